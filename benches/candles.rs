@@ -0,0 +1,81 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use erfurt::{prelude::*, resample::CalendarPeriod};
+
+const SIZES: [usize; 2] = [1_000_000, 10_000_000];
+
+fn bench(c: &mut Criterion, name: &str, f: impl Fn(&mut criterion::Bencher, &Candles)) {
+    let mut group = c.benchmark_group(name);
+    group.sample_size(10);
+    for len in SIZES {
+        let candles = Candles::generate_bench_data(len);
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &candles, &f);
+    }
+    group.finish();
+}
+
+fn push(c: &mut Criterion) {
+    bench(c, "push", |b, candles| {
+        b.iter_batched(
+            || candles.iter().collect::<Vec<_>>(),
+            |rows| {
+                let mut pushed = Candles::with_capacity(candles.id.clone(), rows.len(), true);
+                for candle in rows {
+                    pushed.try_push(candle).unwrap();
+                }
+                pushed
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn iterate(c: &mut Criterion) {
+    bench(c, "iter", |b, candles| {
+        b.iter(|| candles.iter().map(|candle| candle.close).sum::<f64>())
+    });
+    bench(c, "rows", |b, candles| {
+        b.iter(|| candles.rows().map(|row| row.close).sum::<f64>())
+    });
+}
+
+fn slice(c: &mut Criterion) {
+    bench(c, "slice", |b, candles| {
+        let len = candles.time.len();
+        b.iter(|| {
+            (0..len / 1000).fold(0.0, |acc, i| {
+                let slice = candles.slice(i * 1000..(i + 1) * 1000).unwrap();
+                acc + black_box(slice).close[0]
+            })
+        })
+    });
+    bench(c, "take_last", |b, candles| {
+        b.iter(|| candles.take_last(candles.time.len() / 2))
+    });
+}
+
+fn resample(c: &mut Criterion) {
+    bench(c, "resample_calendar_day", |b, candles| {
+        b.iter(|| candles.resample_calendar(CalendarPeriod::Day))
+    });
+    bench(c, "resample_h1", |b, candles| {
+        b.iter(|| candles.resample(Timeframe::H1))
+    });
+}
+
+fn serialize(c: &mut Criterion) {
+    let path = std::env::temp_dir().join(format!("erfurt-bench-{}.bin", std::process::id()));
+    bench(c, "save_bin", |b, candles| {
+        b.iter(|| candles.save_bin(&path).unwrap())
+    });
+    bench(c, "load_bin", |b, candles| {
+        candles.save_bin(&path).unwrap();
+        b.iter(|| Candles::load_bin(&path).unwrap())
+    });
+    let _ = std::fs::remove_file(&path);
+}
+
+criterion_group!(benches, push, iterate, slice, resample, serialize);
+criterion_main!(benches);
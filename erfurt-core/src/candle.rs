@@ -0,0 +1,161 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Candle {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: Option<f64>,
+    pub time: i64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Candles {
+    pub open: Vec<f64>,
+    pub high: Vec<f64>,
+    pub low: Vec<f64>,
+    pub close: Vec<f64>,
+    pub volume: Option<Vec<f64>>,
+    pub time: Vec<i64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PushError {
+    NonIncreasingTime { last: i64, time: i64 },
+    VolumeMismatch { expected: bool },
+}
+
+impl fmt::Display for PushError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PushError::NonIncreasingTime { last, time } => {
+                write!(f, "candle time {time} is not after last time {last}")
+            }
+            PushError::VolumeMismatch { expected: true } => {
+                write!(f, "candle has no volume but series has a volume column")
+            }
+            PushError::VolumeMismatch { expected: false } => {
+                write!(f, "candle has volume but series has no volume column")
+            }
+        }
+    }
+}
+
+impl core::error::Error for PushError {}
+
+/// Start of the `width` wide bucket holding `time`, aligned to zero.
+pub fn floor(time: i64, width: i64) -> i64 {
+    time - time.rem_euclid(width)
+}
+
+impl Candles {
+    pub fn with_capacity(capacity: usize, has_volume: bool) -> Self {
+        Candles {
+            open: Vec::with_capacity(capacity),
+            high: Vec::with_capacity(capacity),
+            low: Vec::with_capacity(capacity),
+            close: Vec::with_capacity(capacity),
+            volume: has_volume.then(|| Vec::with_capacity(capacity)),
+            time: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.time.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.time.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<Candle> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(Candle {
+            open: self.open[index],
+            high: self.high[index],
+            low: self.low[index],
+            close: self.close[index],
+            volume: self.volume.as_ref().map(|xs| xs[index]),
+            time: self.time[index],
+        })
+    }
+
+    pub fn last(&self) -> Option<Candle> {
+        self.get(self.len().checked_sub(1)?)
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Candle> + '_ {
+        (0..self.len()).map(|i| self.get(i).unwrap())
+    }
+
+    /// Appends `candle`, which must be newer than the last bar and match the
+    /// series in having volume. The first candle decides the volume column.
+    pub fn try_push(&mut self, candle: Candle) -> Result<(), PushError> {
+        if self.is_empty() && self.volume.is_none() && candle.volume.is_some() {
+            self.volume = Some(Vec::new());
+        }
+        if let Some(&last) = self.time.last() {
+            if candle.time <= last {
+                return Err(PushError::NonIncreasingTime {
+                    last,
+                    time: candle.time,
+                });
+            }
+        }
+        if candle.volume.is_some() != self.volume.is_some() {
+            return Err(PushError::VolumeMismatch {
+                expected: self.volume.is_some(),
+            });
+        }
+        self.open.push(candle.open);
+        self.high.push(candle.high);
+        self.low.push(candle.low);
+        self.close.push(candle.close);
+        if let (Some(xs), Some(volume)) = (self.volume.as_mut(), candle.volume) {
+            xs.push(volume);
+        }
+        self.time.push(candle.time);
+        Ok(())
+    }
+
+    /// Aggregates into buckets `width` wide aligned to zero, each stamped
+    /// with its start. Bars must be sorted. A non-positive width returns a
+    /// copy.
+    pub fn resample(&self, width: i64) -> Candles {
+        if width <= 0 {
+            return self.clone();
+        }
+        let mut out = Candles::with_capacity(0, self.volume.is_some());
+        for (i, &time) in self.time.iter().enumerate() {
+            let start = floor(time, width);
+            let n = out.len();
+            if out.time.last() == Some(&start) {
+                out.high[n - 1] = out.high[n - 1].max(self.high[i]);
+                out.low[n - 1] = out.low[n - 1].min(self.low[i]);
+                out.close[n - 1] = self.close[i];
+                if let (Some(xs), Some(ys)) = (out.volume.as_mut(), self.volume.as_ref()) {
+                    xs[n - 1] += ys[i];
+                }
+            } else {
+                out.open.push(self.open[i]);
+                out.high.push(self.high[i]);
+                out.low.push(self.low[i]);
+                out.close.push(self.close[i]);
+                if let (Some(xs), Some(ys)) = (out.volume.as_mut(), self.volume.as_ref()) {
+                    xs.push(ys[i]);
+                }
+                out.time.push(start);
+            }
+        }
+        out
+    }
+}
@@ -0,0 +1,13 @@
+//! Standalone `no_std` subset of erfurt: candles with integer timestamps,
+//! pushing and resampling, needing nothing beyond `alloc`. `erfurt` does not
+//! build on these types; it only converts to and from them. Timestamps are
+//! i64 in a unit of the caller's choosing, and bucket widths use the same
+//! unit; the conversions in `erfurt` assume nanoseconds since the Unix epoch.
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod candle;
+
+pub use candle::{Candle, Candles, PushError};
@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::candle::Candles;
+
+/// Actions take effect at `time`; every bar opening before it is adjusted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CorporateAction {
+    /// New shares per old share, so a 2-for-1 split has a ratio of 2.
+    Split { time: DateTime<Utc>, ratio: f64 },
+    /// Cash amount per share going ex on `time`.
+    Dividend { time: DateTime<Utc>, amount: f64 },
+}
+
+impl CorporateAction {
+    pub fn time(&self) -> DateTime<Utc> {
+        match *self {
+            CorporateAction::Split { time, .. } | CorporateAction::Dividend { time, .. } => time,
+        }
+    }
+}
+
+impl Candles {
+    /// Back-adjusts prices so the latest bars stay unchanged. Splits divide
+    /// prices and multiply volume by the ratio; dividends scale prices by
+    /// `1 - amount / close` using the last raw close before the ex-date.
+    /// Extra columns are left untouched.
+    pub fn adjust(&self, actions: &[CorporateAction]) -> Candles {
+        let len = self.time.len();
+        let mut price = vec![1.0; len];
+        let mut volume = vec![1.0; len];
+        for action in actions {
+            let end = self.time.partition_point(|&time| time < action.time());
+            let (price_factor, volume_factor) = match *action {
+                CorporateAction::Split { ratio, .. } => (ratio.recip(), ratio),
+                CorporateAction::Dividend { amount, .. } => match end.checked_sub(1) {
+                    Some(prev) => (1.0 - amount / self.close[prev], 1.0),
+                    None => continue,
+                },
+            };
+            for i in 0..end {
+                price[i] *= price_factor;
+                volume[i] *= volume_factor;
+            }
+        }
+        let scale = |xs: &[f64], factors: &[f64]| {
+            xs.iter()
+                .zip(factors)
+                .map(|(x, f)| x * f)
+                .collect::<Vec<_>>()
+        };
+        Candles {
+            id: self.id.clone(),
+            open: scale(&self.open, &price),
+            high: scale(&self.high, &price),
+            low: scale(&self.low, &price),
+            close: scale(&self.close, &price),
+            volume: self.volume.as_deref().map(|xs| scale(xs, &volume)),
+            time: self.time.clone(),
+            extra: self.extra.clone(),
+            labels: self.labels.clone(),
+            volume_policy: self.volume_policy,
+            instrument: self.instrument,
+        }
+    }
+}
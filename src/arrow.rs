@@ -0,0 +1,189 @@
+use std::{collections::HashMap, fs::File, path::Path, sync::Arc};
+
+use ::arrow::{
+    array::{Array, ArrayRef, Float64Array, RecordBatch, TimestampNanosecondArray},
+    compute::cast,
+    datatypes::{DataType, Field, Schema, TimeUnit},
+    error::ArrowError,
+};
+use chrono::{DateTime, Utc};
+use parquet::{
+    arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ArrowWriter},
+    errors::ParquetError,
+};
+
+use crate::{
+    candle::{Candles, VolumePolicy},
+    extra::ExtraColumns,
+    symbol::Symbol,
+};
+
+const ID_METADATA_KEY: &str = "id";
+
+const COLUMNS: [&str; 6] = ["time", "open", "high", "low", "close", "volume"];
+
+fn schema(candles: &Candles) -> Schema {
+    let mut fields = vec![
+        Field::new(
+            "time",
+            DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())),
+            false,
+        ),
+        Field::new("open", DataType::Float64, false),
+        Field::new("high", DataType::Float64, false),
+        Field::new("low", DataType::Float64, false),
+        Field::new("close", DataType::Float64, false),
+    ];
+    if candles.volume.is_some() {
+        fields.push(Field::new("volume", DataType::Float64, false));
+    }
+    for name in candles.extra.names() {
+        fields.push(Field::new(name, DataType::Float64, true));
+    }
+    let metadata = HashMap::from([(ID_METADATA_KEY.to_string(), candles.id.to_string())]);
+    Schema::new_with_metadata(fields, metadata)
+}
+
+fn float_column(batch: &RecordBatch, name: &str) -> Result<Vec<f64>, ArrowError> {
+    let column = batch
+        .column_by_name(name)
+        .ok_or_else(|| ArrowError::SchemaError(format!("missing column {name}")))?;
+    let column = cast(column, &DataType::Float64)?;
+    let values = column
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .ok_or_else(|| ArrowError::CastError(format!("column {name} is not Float64")))?;
+    Ok(values
+        .iter()
+        .map(|value| value.unwrap_or(f64::NAN))
+        .collect())
+}
+
+fn time_column(batch: &RecordBatch) -> Result<Vec<DateTime<Utc>>, ArrowError> {
+    let column = batch
+        .column_by_name("time")
+        .ok_or_else(|| ArrowError::SchemaError("missing column time".to_string()))?;
+    let column = cast(column, &DataType::Timestamp(TimeUnit::Nanosecond, None))?;
+    let values = column
+        .as_any()
+        .downcast_ref::<TimestampNanosecondArray>()
+        .ok_or_else(|| ArrowError::CastError("column time is not a timestamp".to_string()))?;
+    values
+        .iter()
+        .map(|value| {
+            value
+                .map(DateTime::from_timestamp_nanos)
+                .ok_or_else(|| ArrowError::InvalidArgumentError("null time value".to_string()))
+        })
+        .collect()
+}
+
+impl Candles {
+    pub fn to_arrow_recordbatch(&self) -> Result<RecordBatch, ArrowError> {
+        let time = self
+            .time
+            .iter()
+            .map(|time| {
+                time.timestamp_nanos_opt().ok_or_else(|| {
+                    ArrowError::InvalidArgumentError(format!(
+                        "time {time} is out of the nanosecond range"
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut columns: Vec<ArrayRef> = vec![
+            Arc::new(TimestampNanosecondArray::from(time).with_timezone("UTC")),
+            Arc::new(Float64Array::from(self.open.clone())),
+            Arc::new(Float64Array::from(self.high.clone())),
+            Arc::new(Float64Array::from(self.low.clone())),
+            Arc::new(Float64Array::from(self.close.clone())),
+        ];
+        if let Some(volume) = &self.volume {
+            columns.push(Arc::new(Float64Array::from(volume.clone())));
+        }
+        for (_, xs) in self.extra.iter() {
+            columns.push(Arc::new(Float64Array::from(xs.to_vec())));
+        }
+        RecordBatch::try_new(Arc::new(schema(self)), columns)
+    }
+
+    pub fn from_arrow_recordbatch(batch: &RecordBatch) -> Result<Candles, ArrowError> {
+        let id = batch
+            .schema()
+            .metadata()
+            .get(ID_METADATA_KEY)
+            .map(Symbol::from)
+            .unwrap_or_default();
+        let volume = if batch.column_by_name("volume").is_some() {
+            Some(float_column(batch, "volume")?)
+        } else {
+            None
+        };
+        let mut extra = ExtraColumns::new();
+        for field in batch.schema().fields() {
+            if !COLUMNS.contains(&field.name().as_str()) && field.data_type().is_numeric() {
+                extra.insert(field.name(), float_column(batch, field.name())?);
+            }
+        }
+        Ok(Candles {
+            id,
+            open: float_column(batch, "open")?,
+            high: float_column(batch, "high")?,
+            low: float_column(batch, "low")?,
+            close: float_column(batch, "close")?,
+            volume,
+            time: time_column(batch)?,
+            extra,
+            labels: None,
+            volume_policy: VolumePolicy::Optional,
+            instrument: None,
+        })
+    }
+
+    pub fn to_parquet<P: AsRef<Path>>(&self, path: P) -> Result<(), ParquetError> {
+        let batch = self.to_arrow_recordbatch()?;
+        let file = File::create(path)?;
+        let mut writer = ArrowWriter::try_new(file, batch.schema(), None)?;
+        writer.write(&batch)?;
+        writer.close()?;
+        Ok(())
+    }
+
+    pub fn from_parquet<P: AsRef<Path>>(path: P) -> Result<Candles, ParquetError> {
+        let file = File::open(path)?;
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file)?;
+        let schema = builder.schema().clone();
+        let id = schema
+            .metadata()
+            .get(ID_METADATA_KEY)
+            .map(Symbol::from)
+            .unwrap_or_default();
+        let mut candles = Candles {
+            id: id.clone(),
+            volume: schema.column_with_name("volume").map(|_| Vec::new()),
+            ..Default::default()
+        };
+        for batch in builder.build()? {
+            let chunk = Candles::from_arrow_recordbatch(&batch?)?;
+            if candles.is_empty() {
+                candles = Candles {
+                    id: id.clone(),
+                    ..chunk
+                };
+            } else {
+                candles.open.extend(chunk.open);
+                candles.high.extend(chunk.high);
+                candles.low.extend(chunk.low);
+                candles.close.extend(chunk.close);
+                if let (Some(volume), Some(chunk)) = (&mut candles.volume, chunk.volume) {
+                    volume.extend(chunk);
+                }
+                candles.time.extend(chunk.time);
+                for (name, xs) in candles.extra.iter_mut() {
+                    xs.extend(chunk.extra.get(name).into_iter().flatten());
+                }
+            }
+        }
+        Ok(candles)
+    }
+}
@@ -0,0 +1,175 @@
+//! Chunked downloads of long histories from a [`CandleSource`] into a
+//! [`CandleStore`]. Each chunk is stored as soon as it arrives, so a run
+//! that stops for any reason resumes after the last stored bar. Sleeps on
+//! the Tokio timer.
+
+use std::{fmt, time::Duration};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use tokio::time::{sleep, sleep_until, Instant};
+
+use crate::{
+    source::CandleSource,
+    storage::{CandleStore, StorageError},
+    symbol::Symbol,
+    timeframe::Timeframe,
+};
+
+#[derive(Debug)]
+pub enum BackfillError<E> {
+    /// The source failed with an error that is not transient, or retries ran
+    /// out.
+    Source(E),
+    Storage(StorageError),
+    /// The timeframe is not positive, so no request would advance the run.
+    InvalidTimeframe(Timeframe),
+}
+
+impl<E: fmt::Display> fmt::Display for BackfillError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackfillError::Source(err) => write!(f, "{err}"),
+            BackfillError::Storage(err) => write!(f, "{err}"),
+            BackfillError::InvalidTimeframe(timeframe) => write!(
+                f,
+                "cannot backfill with a timeframe of {} seconds",
+                timeframe.as_seconds()
+            ),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for BackfillError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BackfillError::Source(err) => Some(err),
+            BackfillError::Storage(err) => Some(err),
+            BackfillError::InvalidTimeframe(_) => None,
+        }
+    }
+}
+
+impl<E> From<StorageError> for BackfillError<E> {
+    fn from(err: StorageError) -> Self {
+        BackfillError::Storage(err)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BackfillReport {
+    /// Where the run started, after the last stored bar when resuming.
+    pub resumed_from: Option<DateTime<Utc>>,
+    pub requests: usize,
+    pub retries: usize,
+    pub stored: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Backfiller<S: CandleSource> {
+    source: S,
+    chunk: usize,
+    interval: Duration,
+    retries: usize,
+    backoff: Duration,
+    is_transient: fn(&S::Error) -> bool,
+}
+
+impl<S: CandleSource> Backfiller<S> {
+    /// Chunks of 1000 bars, at most one request per 100ms and 5 retries
+    /// starting at a 1s backoff. Every error counts as transient.
+    pub fn new(source: S) -> Self {
+        Backfiller {
+            source,
+            chunk: 1000,
+            interval: Duration::from_millis(100),
+            retries: 5,
+            backoff: Duration::from_secs(1),
+            is_transient: |_| true,
+        }
+    }
+
+    /// Bars per request, at least one.
+    pub fn with_chunk(mut self, bars: usize) -> Self {
+        self.chunk = bars.max(1);
+        self
+    }
+
+    /// Least time between the starts of two requests.
+    pub fn with_rate_limit(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Retries of a failed request, waiting `backoff` and doubling it after
+    /// each attempt.
+    pub fn with_retries(mut self, retries: usize, backoff: Duration) -> Self {
+        self.retries = retries;
+        self.backoff = backoff;
+        self
+    }
+
+    /// Which source errors to retry; the others end the run at once.
+    pub fn with_transient(mut self, is_transient: fn(&S::Error) -> bool) -> Self {
+        self.is_transient = is_transient;
+        self
+    }
+
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// Downloads the bars of `symbol` opening in `start..end` into `store`,
+    /// starting after the newest stored bar when there is one. The store is
+    /// keyed by symbol alone, so keep one timeframe per symbol there.
+    pub async fn run(
+        &self,
+        store: &mut CandleStore,
+        symbol: &Symbol,
+        timeframe: Timeframe,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<BackfillReport, BackfillError<S::Error>> {
+        if timeframe.as_seconds() <= 0 {
+            return Err(BackfillError::InvalidTimeframe(timeframe));
+        }
+        let step = timeframe.duration();
+        let span = step
+            .checked_mul(i32::try_from(self.chunk).unwrap_or(i32::MAX))
+            .unwrap_or(TimeDelta::MAX);
+        if span <= TimeDelta::zero() {
+            return Err(BackfillError::InvalidTimeframe(timeframe));
+        }
+        let mut report = BackfillReport::default();
+        let mut from = start;
+        if let Some(last) = store.last_time(symbol)? {
+            if last >= start {
+                from = last + step;
+                report.resumed_from = Some(from);
+            }
+        }
+        let mut next_request = Instant::now();
+        while from < end {
+            let to = from.checked_add_signed(span).map_or(end, |to| to.min(end));
+            let mut backoff = self.backoff;
+            let mut attempt = 0;
+            let candles = loop {
+                sleep_until(next_request).await;
+                next_request = Instant::now() + self.interval;
+                report.requests += 1;
+                match self.source.fetch(symbol, timeframe, from..to).await {
+                    Ok(candles) => break candles,
+                    Err(err) if attempt < self.retries && (self.is_transient)(&err) => {
+                        attempt += 1;
+                        report.retries += 1;
+                        sleep(backoff).await;
+                        backoff = backoff.saturating_mul(2);
+                    }
+                    Err(err) => return Err(BackfillError::Source(err)),
+                }
+            };
+            report.stored += store.upsert(&candles)?;
+            from = to;
+        }
+        Ok(report)
+    }
+}
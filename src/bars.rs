@@ -0,0 +1,359 @@
+use chrono::{DateTime, Utc};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    candle::{Candle, Candles, CandlesExt},
+    extra::{self, ExtraColumns},
+    symbol::Symbol,
+    timeframe::Timeframe,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BarSpec {
+    Volume(f64),
+    Dollar(f64),
+    Ticks(usize),
+    /// Closes a bar once its high-low range reaches this size.
+    Range(f64),
+}
+
+/// Side of the aggressor, the party whose order crossed the spread.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Trade {
+    pub price: f64,
+    pub size: f64,
+    pub time: DateTime<Utc>,
+    /// Aggressor side, when the source reports it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub side: Option<Side>,
+}
+
+impl Trade {
+    fn flow(&self) -> (f64, f64) {
+        match self.side {
+            Some(Side::Buy) => (self.size, 0.0),
+            Some(Side::Sell) => (0.0, self.size),
+            None => (0.0, 0.0),
+        }
+    }
+}
+
+/// Aggressor-side volume of one bar. Trades without a side count toward
+/// neither side.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct OrderFlow {
+    pub buy_volume: f64,
+    pub sell_volume: f64,
+    /// Delta summed over every bar up to and including this one.
+    pub cumulative_delta: f64,
+}
+
+impl OrderFlow {
+    /// Buy minus sell volume of the bar.
+    pub fn delta(&self) -> f64 {
+        self.buy_volume - self.sell_volume
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Bar {
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+    buy: f64,
+    sell: f64,
+    time: DateTime<Utc>,
+}
+
+impl Bar {
+    fn merge(&mut self, other: &Bar) {
+        self.high = self.high.max(other.high);
+        self.low = self.low.min(other.low);
+        self.close = other.close;
+        self.volume += other.volume;
+        self.buy += other.buy;
+        self.sell += other.sell;
+    }
+}
+
+impl BarSpec {
+    fn threshold(&self) -> f64 {
+        match *self {
+            BarSpec::Volume(volume) => volume,
+            BarSpec::Dollar(dollar) => dollar,
+            BarSpec::Ticks(ticks) => ticks as f64,
+            BarSpec::Range(range) => range,
+        }
+    }
+
+    fn progress(&self, bar: &Bar) -> f64 {
+        match self {
+            BarSpec::Volume(_) => bar.volume,
+            BarSpec::Dollar(_) => bar.close * bar.volume,
+            BarSpec::Ticks(_) => 1.0,
+            BarSpec::Range(_) => 0.0,
+        }
+    }
+
+    fn is_complete(&self, bar: &Bar, progress: f64) -> bool {
+        match self {
+            BarSpec::Range(range) => bar.high - bar.low >= *range,
+            _ => progress >= self.threshold(),
+        }
+    }
+}
+
+/// Running sum of `buy - sell`.
+pub(crate) fn cumulative_delta(buy: &[f64], sell: &[f64]) -> Vec<f64> {
+    buy.iter()
+        .zip(sell)
+        .scan(0.0, |total, (buy, sell)| {
+            *total += buy - sell;
+            Some(*total)
+        })
+        .collect()
+}
+
+/// Aggregates `rows` into the empty series `bars`, which must have a volume
+/// column, adding the order flow columns when `flow` is set.
+fn aggregate(
+    mut bars: Candles,
+    spec: BarSpec,
+    rows: impl Iterator<Item = Bar>,
+    flow: bool,
+) -> Candles {
+    let (mut buy, mut sell) = (Vec::new(), Vec::new());
+    let mut push = |bar: Bar| {
+        bars.push_row(
+            bar.open,
+            bar.high,
+            bar.low,
+            bar.close,
+            Some(bar.volume),
+            bar.time,
+        );
+        buy.push(bar.buy);
+        sell.push(bar.sell);
+    };
+    let mut current: Option<Bar> = None;
+    let mut progress = 0.0;
+    for row in rows {
+        match current.as_mut() {
+            Some(bar) => bar.merge(&row),
+            None => current = Some(row),
+        }
+        progress += spec.progress(&row);
+        if current.is_some_and(|bar| spec.is_complete(&bar, progress)) {
+            if let Some(bar) = current.take() {
+                push(bar);
+            }
+            progress = 0.0;
+        }
+    }
+    if let Some(bar) = current {
+        push(bar);
+    }
+    if flow {
+        bars.extra
+            .insert(extra::CUMULATIVE_DELTA, cumulative_delta(&buy, &sell));
+        bars.extra.insert(extra::BUY_VOLUME, buy);
+        bars.extra.insert(extra::SELL_VOLUME, sell);
+    }
+    bars
+}
+
+impl Candles {
+    /// Re-aggregates bars into information-driven bars. The last bar may be
+    /// incomplete. Returns `None` for volume and dollar bars without a volume
+    /// column. Buy and sell volume columns are summed into the new bars, whose
+    /// cumulative delta starts from zero; other extra columns and labels are
+    /// dropped, while the volume policy and instrument are kept.
+    pub fn rebar(&self, spec: BarSpec) -> Option<Candles> {
+        let volume = match (&self.volume, spec) {
+            (Some(volume), _) => volume.clone(),
+            (None, BarSpec::Ticks(_) | BarSpec::Range(_)) => vec![0.0; self.time.len()],
+            (None, _) => return None,
+        };
+        let flow = self.buy_volume().zip(self.sell_volume());
+        let rows = (0..self.time.len()).map(|i| Bar {
+            open: self.open[i],
+            high: self.high[i],
+            low: self.low[i],
+            close: self.close[i],
+            volume: volume[i],
+            buy: flow.map_or(0.0, |(buy, _)| buy[i]),
+            sell: flow.map_or(0.0, |(_, sell)| sell[i]),
+            time: self.time[i],
+        });
+        let empty = Candles {
+            volume: Some(Vec::new()),
+            extra: ExtraColumns::new(),
+            labels: None,
+            ..self.empty_like()
+        };
+        let mut bars = aggregate(empty, spec, rows, flow.is_some());
+        if self.volume.is_none() {
+            bars.volume = None;
+        }
+        Some(bars)
+    }
+
+    /// Bars closing once their high-low range reaches `range`. Source bars
+    /// are not split, so a bar that jumps past the range overshoots it. Keeps
+    /// the volume policy and instrument, as [`Candles::rebar`] does.
+    pub fn to_range_bars(&self, range: f64) -> Candles {
+        self.rebar(BarSpec::Range(range))
+            .expect("range bars do not need volume")
+    }
+
+    /// Adds the buy volume, sell volume and cumulative delta columns when
+    /// any trade reports its aggressor side. With no source series, the
+    /// volume policy and instrument are the defaults; set them on the result.
+    pub fn from_trades(id: impl Into<Symbol>, trades: &[Trade], spec: BarSpec) -> Candles {
+        let rows = trades.iter().map(|trade| {
+            let (buy, sell) = trade.flow();
+            Bar {
+                open: trade.price,
+                high: trade.price,
+                low: trade.price,
+                close: trade.price,
+                volume: trade.size,
+                buy,
+                sell,
+                time: trade.time,
+            }
+        });
+        let flow = trades.iter().any(|trade| trade.side.is_some());
+        aggregate(Candles::with_capacity(id, 0, true), spec, rows, flow)
+    }
+
+    /// Buy minus sell volume of each bar, if the series has both columns.
+    pub fn delta(&self) -> Option<Vec<f64>> {
+        let (buy, sell) = self.buy_volume().zip(self.sell_volume())?;
+        Some(buy.iter().zip(sell).map(|(buy, sell)| buy - sell).collect())
+    }
+
+    /// Pushes `candle` with its order flow, adding the flow columns, filled
+    /// with NaN for earlier bars, if the series lacks them.
+    pub fn try_push_with_flow(
+        &mut self,
+        candle: Candle,
+        flow: OrderFlow,
+    ) -> Result<(), crate::candle::PushError> {
+        self.try_push(candle)?;
+        let len = self.time.len();
+        let values = [
+            (extra::BUY_VOLUME, flow.buy_volume),
+            (extra::SELL_VOLUME, flow.sell_volume),
+            (extra::CUMULATIVE_DELTA, flow.cumulative_delta),
+        ];
+        for (name, value) in values {
+            if !self.extra.contains(name) {
+                self.extra.insert(name, vec![f64::NAN; len]);
+            }
+            if let Some(last) = self.extra.get_mut(name).and_then(|xs| xs.last_mut()) {
+                *last = value;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Builds time bars from trades as they arrive. Bars are stamped with the
+/// start of their `timeframe` bucket counted from the Unix epoch.
+#[derive(Clone, Debug)]
+pub struct TimeBarBuilder {
+    id: Symbol,
+    timeframe: Timeframe,
+    current: Option<Candle>,
+    flow: OrderFlow,
+}
+
+impl TimeBarBuilder {
+    pub fn new(id: impl Into<Symbol>, timeframe: Timeframe) -> Self {
+        TimeBarBuilder {
+            id: id.into(),
+            timeframe,
+            current: None,
+            flow: OrderFlow::default(),
+        }
+    }
+
+    /// The bar still forming.
+    pub fn current(&self) -> Option<&Candle> {
+        self.current.as_ref()
+    }
+
+    /// Order flow of the bar still forming.
+    pub fn current_flow(&self) -> Option<OrderFlow> {
+        self.current.as_ref().map(|_| self.flow)
+    }
+
+    /// Adds `trade`, returning the previous bar once a trade opens a later
+    /// bucket. Trades older than the forming bar are folded into it.
+    pub fn push(&mut self, trade: &Trade) -> Option<Candle> {
+        self.push_with_flow(trade).map(|(candle, _)| candle)
+    }
+
+    /// [`TimeBarBuilder::push`], also returning the order flow of the bar.
+    pub fn push_with_flow(&mut self, trade: &Trade) -> Option<(Candle, OrderFlow)> {
+        let timeframe = Timeframe::seconds(self.timeframe.as_seconds().max(1));
+        let start = timeframe.floor(trade.time);
+        let (buy, sell) = trade.flow();
+        let completed = match self.current.as_mut() {
+            Some(bar) if start <= bar.time => {
+                bar.high = bar.high.max(trade.price);
+                bar.low = bar.low.min(trade.price);
+                bar.close = trade.price;
+                bar.volume = Some(bar.volume.unwrap_or(0.0) + trade.size);
+                None
+            }
+            _ => {
+                let flow = self.flow;
+                self.flow.buy_volume = 0.0;
+                self.flow.sell_volume = 0.0;
+                self.current
+                    .replace(Candle {
+                        open: trade.price,
+                        high: trade.price,
+                        low: trade.price,
+                        close: trade.price,
+                        volume: Some(trade.size),
+                        time: start,
+                        id: self.id.clone(),
+                    })
+                    .map(|candle| (candle, flow))
+            }
+        };
+        self.flow.buy_volume += buy;
+        self.flow.sell_volume += sell;
+        self.flow.cumulative_delta += buy - sell;
+        completed
+    }
+
+    /// Takes the forming bar, as when the trade source ends.
+    pub fn finish(&mut self) -> Option<Candle> {
+        self.finish_with_flow().map(|(candle, _)| candle)
+    }
+
+    /// [`TimeBarBuilder::finish`], also returning the order flow of the bar.
+    pub fn finish_with_flow(&mut self) -> Option<(Candle, OrderFlow)> {
+        let candle = self.current.take()?;
+        let flow = self.flow;
+        self.flow.buy_volume = 0.0;
+        self.flow.sell_volume = 0.0;
+        Some((candle, flow))
+    }
+}
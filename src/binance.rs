@@ -0,0 +1,189 @@
+//! [`CandleSource`] over the public Binance spot REST API. Needs a Tokio
+//! runtime, as reqwest does.
+
+use std::{fmt, ops::Range};
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    candle::Candles, exchanges::BinanceKline, merge::MergeError, source::CandleSource,
+    symbol::Symbol, timeframe::Timeframe,
+};
+
+pub const BASE_URL: &str = "https://api.binance.com";
+
+/// Most klines the endpoint returns per request.
+pub const MAX_LIMIT: usize = 1000;
+
+#[derive(Debug)]
+pub enum BinanceError {
+    Http(reqwest::Error),
+    /// Binance has no interval of this length.
+    UnsupportedTimeframe(Timeframe),
+    Merge(MergeError),
+}
+
+impl fmt::Display for BinanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinanceError::Http(err) => write!(f, "{err}"),
+            BinanceError::UnsupportedTimeframe(timeframe) => {
+                write!(f, "binance has no {}s interval", timeframe.as_seconds())
+            }
+            BinanceError::Merge(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for BinanceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BinanceError::Http(err) => Some(err),
+            BinanceError::UnsupportedTimeframe(_) => None,
+            BinanceError::Merge(err) => Some(err),
+        }
+    }
+}
+
+impl BinanceError {
+    /// Timeouts, failed connections, rate limiting and server errors, which
+    /// are worth retrying.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            BinanceError::Http(err) => {
+                err.is_timeout()
+                    || err.is_connect()
+                    || err.status().is_some_and(|status| {
+                        status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                    })
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<reqwest::Error> for BinanceError {
+    fn from(err: reqwest::Error) -> Self {
+        BinanceError::Http(err)
+    }
+}
+
+impl From<MergeError> for BinanceError {
+    fn from(err: MergeError) -> Self {
+        BinanceError::Merge(err)
+    }
+}
+
+/// The kline interval name for `timeframe`.
+pub fn interval(timeframe: Timeframe) -> Option<&'static str> {
+    Some(match timeframe.as_seconds() {
+        1 => "1s",
+        60 => "1m",
+        180 => "3m",
+        300 => "5m",
+        900 => "15m",
+        1800 => "30m",
+        3600 => "1h",
+        7200 => "2h",
+        14400 => "4h",
+        21600 => "6h",
+        28800 => "8h",
+        43200 => "12h",
+        86400 => "1d",
+        259200 => "3d",
+        604800 => "1w",
+        _ => return None,
+    })
+}
+
+#[derive(Clone, Debug)]
+pub struct BinanceSource {
+    client: reqwest::Client,
+    base_url: String,
+    limit: usize,
+}
+
+impl Default for BinanceSource {
+    fn default() -> Self {
+        BinanceSource::new(reqwest::Client::new())
+    }
+}
+
+impl BinanceSource {
+    pub fn new(client: reqwest::Client) -> Self {
+        BinanceSource {
+            client,
+            base_url: BASE_URL.to_string(),
+            limit: MAX_LIMIT,
+        }
+    }
+
+    /// For mirrors and the testnet.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Klines per request, clamped to `1..=MAX_LIMIT`.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit.clamp(1, MAX_LIMIT);
+        self
+    }
+
+    async fn page(
+        &self,
+        symbol: &Symbol,
+        interval: &str,
+        start: i64,
+        end: i64,
+    ) -> Result<Vec<BinanceKline>, BinanceError> {
+        let klines = self
+            .client
+            .get(format!("{}/api/v3/klines", self.base_url))
+            .query(&[
+                ("symbol", symbol.as_str().to_string()),
+                ("interval", interval.to_string()),
+                ("startTime", start.to_string()),
+                ("endTime", end.to_string()),
+                ("limit", self.limit.to_string()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(klines)
+    }
+}
+
+impl CandleSource for BinanceSource {
+    type Error = BinanceError;
+
+    /// Pages forward from the start of `range`, one request per `limit`
+    /// klines.
+    async fn fetch(
+        &self,
+        symbol: &Symbol,
+        timeframe: Timeframe,
+        range: Range<DateTime<Utc>>,
+    ) -> Result<Candles, BinanceError> {
+        let interval = interval(timeframe).ok_or(BinanceError::UnsupportedTimeframe(timeframe))?;
+        let mut candles = Candles::with_capacity(symbol.clone(), 0, true);
+        // The endpoint's end time is inclusive.
+        let end = range.end.timestamp_millis() - 1;
+        let mut start = range.start.timestamp_millis();
+        while start <= end {
+            let klines = self.page(symbol, interval, start, end).await?;
+            let Some(last) = klines.last() else {
+                break;
+            };
+            start = last.open_time.timestamp_millis() + 1;
+            let full = klines.len() == self.limit;
+            candles.append(&Candles::from_binance_klines(symbol.clone(), &klines))?;
+            if !full {
+                break;
+            }
+        }
+        Ok(candles)
+    }
+}
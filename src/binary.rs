@@ -0,0 +1,374 @@
+//! Little-endian layout: 32 byte header (magic, version, flags, bar count, id
+//! length), the id padded to 8 bytes, then the time column as i64 nanoseconds
+//! followed by the open, high, low, close and optional volume f64 columns.
+//! Extra columns follow as a u64 count and, per column, a u64 name length, the
+//! name padded to 8 bytes and the f64 values.
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    ops::Range,
+    path::Path,
+};
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    candle::{Candles, VolumePolicy},
+    extra::ExtraColumns,
+    symbol::Symbol,
+};
+
+const MAGIC: &[u8; 8] = b"ERFURTB\0";
+const VERSION: u32 = 1;
+const HAS_VOLUME: u32 = 1;
+const HAS_EXTRA: u32 = 2;
+const HEADER_LEN: usize = 32;
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Header {
+    pub(crate) id: Symbol,
+    pub(crate) len: usize,
+    pub(crate) has_volume: bool,
+    pub(crate) data: usize,
+    pub(crate) extra: Vec<(String, usize)>,
+}
+
+impl Header {
+    pub(crate) fn parse(bytes: &[u8]) -> io::Result<Header> {
+        if bytes.len() < HEADER_LEN || &bytes[..8] != MAGIC {
+            return Err(invalid("not an erfurt binary file"));
+        }
+        let u32_at = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+        let u64_at = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap());
+        if u32_at(8) != VERSION {
+            return Err(invalid("unsupported erfurt binary version"));
+        }
+        let flags = u32_at(12);
+        let has_volume = flags & HAS_VOLUME != 0;
+        let len = usize::try_from(u64_at(16)).map_err(|_| invalid("bar count overflow"))?;
+        let id_len = usize::try_from(u64_at(24)).map_err(|_| invalid("id length overflow"))?;
+        let id_end = HEADER_LEN
+            .checked_add(id_len)
+            .ok_or_else(|| invalid("id length overflow"))?;
+        let id = bytes
+            .get(HEADER_LEN..id_end)
+            .ok_or_else(|| invalid("truncated id"))?;
+        let id = std::str::from_utf8(id)
+            .map_err(|_| invalid("id is not utf-8"))?
+            .into();
+        let data = id_end
+            .checked_next_multiple_of(8)
+            .ok_or_else(|| invalid("id length overflow"))?;
+        let columns = if has_volume { 6 } else { 5 };
+        let column_len = len
+            .checked_mul(8)
+            .ok_or_else(|| invalid("bar count overflow"))?;
+        let mut at = column_len
+            .checked_mul(columns)
+            .and_then(|size| size.checked_add(data))
+            .ok_or_else(|| invalid("bar count overflow"))?;
+        if bytes.len() < at {
+            return Err(invalid("truncated column data"));
+        }
+        let mut extra = Vec::new();
+        if flags & HAS_EXTRA != 0 {
+            let overflow = || invalid("extra column overflow");
+            let read_u64 = |at: &mut usize| {
+                let end = at.checked_add(8).ok_or_else(overflow)?;
+                let value = bytes
+                    .get(*at..end)
+                    .ok_or_else(|| invalid("truncated extra columns"))?;
+                *at = end;
+                usize::try_from(u64::from_le_bytes(value.try_into().unwrap()))
+                    .map_err(|_| overflow())
+            };
+            let count = read_u64(&mut at)?;
+            for _ in 0..count {
+                let name_len = read_u64(&mut at)?;
+                let name_end = at.checked_add(name_len).ok_or_else(overflow)?;
+                let name = bytes
+                    .get(at..name_end)
+                    .ok_or_else(|| invalid("truncated extra column name"))?;
+                let name = String::from_utf8(name.to_vec())
+                    .map_err(|_| invalid("extra column name is not utf-8"))?;
+                at = name_end.checked_next_multiple_of(8).ok_or_else(overflow)?;
+                let end = at.checked_add(column_len).ok_or_else(overflow)?;
+                if bytes.len() < end {
+                    return Err(invalid("truncated extra column data"));
+                }
+                extra.push((name, at));
+                at = end;
+            }
+        }
+        Ok(Header {
+            id,
+            len,
+            has_volume,
+            data,
+            extra,
+        })
+    }
+
+    pub(crate) fn column(&self, index: usize) -> Range<usize> {
+        let start = self.data + index * self.len * 8;
+        start..start + self.len * 8
+    }
+
+    pub(crate) fn extra(&self, bytes: &[u8]) -> ExtraColumns {
+        let mut extra = ExtraColumns::new();
+        for (name, start) in &self.extra {
+            extra.insert(name.clone(), to_f64s(&bytes[*start..*start + self.len * 8]));
+        }
+        extra
+    }
+}
+
+fn to_f64s(bytes: &[u8]) -> Vec<f64> {
+    bytes
+        .chunks_exact(8)
+        .map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap()))
+        .collect()
+}
+
+pub(crate) fn decode(bytes: &[u8]) -> io::Result<Candles> {
+    let header = Header::parse(bytes)?;
+    Ok(Candles {
+        time: decode_time(&bytes[header.column(0)]),
+        open: to_f64s(&bytes[header.column(1)]),
+        high: to_f64s(&bytes[header.column(2)]),
+        low: to_f64s(&bytes[header.column(3)]),
+        close: to_f64s(&bytes[header.column(4)]),
+        volume: header.has_volume.then(|| to_f64s(&bytes[header.column(5)])),
+        extra: header.extra(bytes),
+        labels: None,
+        id: header.id,
+        volume_policy: VolumePolicy::Optional,
+        instrument: None,
+    })
+}
+
+pub(crate) fn decode_time(bytes: &[u8]) -> Vec<DateTime<Utc>> {
+    bytes
+        .chunks_exact(8)
+        .map(|chunk| DateTime::from_timestamp_nanos(i64::from_le_bytes(chunk.try_into().unwrap())))
+        .collect()
+}
+
+fn write_padded<W: Write>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
+    writer.write_all(bytes)?;
+    let padding = bytes.len().next_multiple_of(8) - bytes.len();
+    writer.write_all(&[0; 8][..padding])
+}
+
+fn write_f64s<W: Write>(writer: &mut W, values: &[f64]) -> io::Result<()> {
+    for value in values {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+/// Rejects a series with a column shorter or longer than the time column,
+/// which would not decode, before anything is written.
+pub(crate) fn encode<W: Write>(candles: &Candles, mut writer: W) -> io::Result<()> {
+    let len = candles.time.len();
+    let columns = [&candles.open, &candles.high, &candles.low, &candles.close];
+    let mismatched = columns
+        .into_iter()
+        .chain(candles.volume.as_ref())
+        .map(|xs| xs.len())
+        .chain(candles.extra.iter().map(|(_, xs)| xs.len()))
+        .any(|n| n != len);
+    if mismatched {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "column length does not match bar count",
+        ));
+    }
+    let mut flags = 0;
+    if candles.volume.is_some() {
+        flags |= HAS_VOLUME;
+    }
+    if !candles.extra.is_empty() {
+        flags |= HAS_EXTRA;
+    }
+    writer.write_all(MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&flags.to_le_bytes())?;
+    writer.write_all(&(candles.time.len() as u64).to_le_bytes())?;
+    writer.write_all(&(candles.id.len() as u64).to_le_bytes())?;
+    write_padded(&mut writer, candles.id.as_bytes())?;
+    for time in &candles.time {
+        let nanos = time.timestamp_nanos_opt().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "time out of the nanosecond range",
+            )
+        })?;
+        writer.write_all(&nanos.to_le_bytes())?;
+    }
+    for column in columns.into_iter().chain(candles.volume.as_ref()) {
+        write_f64s(&mut writer, column)?;
+    }
+    if !candles.extra.is_empty() {
+        writer.write_all(&(candles.extra.len() as u64).to_le_bytes())?;
+        for (name, column) in candles.extra.iter() {
+            writer.write_all(&(name.len() as u64).to_le_bytes())?;
+            write_padded(&mut writer, name.as_bytes())?;
+            write_f64s(&mut writer, column)?;
+        }
+    }
+    writer.flush()
+}
+
+impl Candles {
+    pub fn save_bin<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        encode(self, BufWriter::new(File::create(path)?))
+    }
+
+    pub fn load_bin<P: AsRef<Path>>(path: P) -> io::Result<Candles> {
+        decode(&std::fs::read(path)?)
+    }
+}
+
+#[cfg(feature = "mmap")]
+pub use mapped::MappedCandles;
+
+#[cfg(feature = "mmap")]
+mod mapped {
+    use std::{fs::File, io, path::Path};
+
+    use chrono::{DateTime, Utc};
+    use memmap2::Mmap;
+
+    use super::{decode_time, invalid, Header};
+    use crate::{
+        candle::{Candle, Candles, CandlesExt, VolumePolicy},
+        extra::ExtraColumns,
+        slice::CandlesSlice,
+    };
+
+    /// Price and volume columns are read straight from the mapping; the time
+    /// and extra columns are decoded once on open.
+    #[derive(Debug)]
+    pub struct MappedCandles {
+        mmap: Mmap,
+        header: Header,
+        time: Vec<DateTime<Utc>>,
+        extra: ExtraColumns,
+    }
+
+    impl MappedCandles {
+        pub fn open<P: AsRef<Path>>(path: P) -> io::Result<MappedCandles> {
+            if cfg!(target_endian = "big") {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "memory-mapped candles require a little-endian target",
+                ));
+            }
+            let file = File::open(path)?;
+            // SAFETY: the mapping is read-only; modifying the file while it is
+            // mapped is undefined behaviour, as with any mmap.
+            let mmap = unsafe { Mmap::map(&file)? };
+            let header = Header::parse(&mmap)?;
+            if header.data % std::mem::align_of::<f64>() != 0 {
+                return Err(invalid("misaligned column data"));
+            }
+            let time = decode_time(&mmap[header.column(0)]);
+            let extra = header.extra(&mmap);
+            Ok(MappedCandles {
+                mmap,
+                header,
+                time,
+                extra,
+            })
+        }
+
+        fn column(&self, index: usize) -> &[f64] {
+            let bytes = &self.mmap[self.header.column(index)];
+            // SAFETY: the header was validated to cover `len` f64 values, the
+            // mapping is page aligned and `data` is a multiple of 8, and the
+            // target is little-endian.
+            unsafe { std::slice::from_raw_parts(bytes.as_ptr().cast::<f64>(), self.header.len) }
+        }
+
+        pub fn id(&self) -> &str {
+            &self.header.id
+        }
+
+        pub fn len(&self) -> usize {
+            self.header.len
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.header.len == 0
+        }
+
+        pub fn as_slice(&self) -> CandlesSlice<'_> {
+            CandlesSlice {
+                id: &self.header.id,
+                open: self.column(1),
+                high: self.column(2),
+                low: self.column(3),
+                close: self.column(4),
+                volume: self.header.has_volume.then(|| self.column(5)),
+                time: &self.time,
+                labels: None,
+                extra: &self.extra,
+                offset: 0,
+                volume_policy: VolumePolicy::Optional,
+                instrument: None,
+            }
+        }
+
+        pub fn to_candles(&self) -> Candles {
+            self.as_slice().to_candles()
+        }
+    }
+
+    impl CandlesExt for MappedCandles {
+        fn get(&self, index: usize) -> Option<Candle> {
+            self.as_slice().get(index)
+        }
+
+        fn open(&self) -> &[f64] {
+            self.column(1)
+        }
+
+        fn high(&self) -> &[f64] {
+            self.column(2)
+        }
+
+        fn low(&self) -> &[f64] {
+            self.column(3)
+        }
+
+        fn close(&self) -> &[f64] {
+            self.column(4)
+        }
+
+        fn volume(&self) -> Option<&[f64]> {
+            self.header.has_volume.then(|| self.column(5))
+        }
+
+        fn time(&self) -> &[DateTime<Utc>] {
+            &self.time
+        }
+
+        fn extra(&self, name: &str) -> Option<&[f64]> {
+            self.extra.get(name)
+        }
+
+        fn last(&self) -> Option<Candle> {
+            self.as_slice().last()
+        }
+
+        fn take_last(&self, n: usize) -> Option<Candles> {
+            self.as_slice().take_last(n)
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! MessagePack and bincode blobs for caches. Every blob starts with an
+//! 8 byte frame: the magic `ERFB`, a format byte, a reserved byte and the
+//! u16 little-endian layout version, so a blob written by an older release
+//! is recognized and one from a newer release is rejected instead of
+//! misread. Times are stored as i64 nanoseconds.
+
+use std::fmt;
+
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    candle::{Candles, VolumePolicy},
+    extra::ExtraColumns,
+};
+
+const MAGIC: &[u8; 4] = b"ERFB";
+const VERSION: u16 = 1;
+const FRAME_LEN: usize = 8;
+
+#[cfg(feature = "msgpack")]
+const MSGPACK: u8 = 1;
+#[cfg(feature = "bincode")]
+const BINCODE: u8 = 2;
+
+#[derive(Debug)]
+pub enum BlobError {
+    /// Missing or foreign frame.
+    NotABlob,
+    WrongFormat {
+        expected: u8,
+        found: u8,
+    },
+    UnsupportedVersion(u16),
+    /// A time outside the nanosecond range on encode.
+    TimeOutOfRange,
+    #[cfg(feature = "msgpack")]
+    MsgpackEncode(rmp_serde::encode::Error),
+    #[cfg(feature = "msgpack")]
+    MsgpackDecode(rmp_serde::decode::Error),
+    #[cfg(feature = "bincode")]
+    BincodeEncode(bincode::error::EncodeError),
+    #[cfg(feature = "bincode")]
+    BincodeDecode(bincode::error::DecodeError),
+}
+
+impl fmt::Display for BlobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlobError::NotABlob => write!(f, "not an erfurt blob"),
+            BlobError::WrongFormat { expected, found } => {
+                write!(f, "blob format {found}, expected {expected}")
+            }
+            BlobError::UnsupportedVersion(version) => {
+                write!(f, "unsupported blob version {version}")
+            }
+            BlobError::TimeOutOfRange => write!(f, "time out of the nanosecond range"),
+            #[cfg(feature = "msgpack")]
+            BlobError::MsgpackEncode(err) => write!(f, "{err}"),
+            #[cfg(feature = "msgpack")]
+            BlobError::MsgpackDecode(err) => write!(f, "{err}"),
+            #[cfg(feature = "bincode")]
+            BlobError::BincodeEncode(err) => write!(f, "{err}"),
+            #[cfg(feature = "bincode")]
+            BlobError::BincodeDecode(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for BlobError {}
+
+/// Field layout of version 1, without the `skip_serializing_if` attributes
+/// of `Candles` that formats lacking field names cannot round-trip.
+#[derive(Serialize)]
+struct BlobRef<'a> {
+    id: &'a str,
+    time: Vec<i64>,
+    open: &'a [f64],
+    high: &'a [f64],
+    low: &'a [f64],
+    close: &'a [f64],
+    volume: Option<&'a [f64]>,
+    extra: &'a ExtraColumns,
+}
+
+#[derive(Deserialize)]
+struct Blob {
+    id: String,
+    time: Vec<i64>,
+    open: Vec<f64>,
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+    volume: Option<Vec<f64>>,
+    extra: ExtraColumns,
+}
+
+fn frame(format: u8) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(FRAME_LEN);
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&[format, 0]);
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    bytes
+}
+
+/// Checks the frame and returns the payload after it.
+fn unframe(bytes: &[u8], format: u8) -> Result<&[u8], BlobError> {
+    if bytes.len() < FRAME_LEN || &bytes[..4] != MAGIC {
+        return Err(BlobError::NotABlob);
+    }
+    if bytes[4] != format {
+        return Err(BlobError::WrongFormat {
+            expected: format,
+            found: bytes[4],
+        });
+    }
+    let version = u16::from_le_bytes([bytes[6], bytes[7]]);
+    if version == 0 || version > VERSION {
+        return Err(BlobError::UnsupportedVersion(version));
+    }
+    Ok(&bytes[FRAME_LEN..])
+}
+
+impl Candles {
+    fn to_blob(&self) -> Result<BlobRef<'_>, BlobError> {
+        Ok(BlobRef {
+            id: &self.id,
+            time: self
+                .time
+                .iter()
+                .map(|time| time.timestamp_nanos_opt().ok_or(BlobError::TimeOutOfRange))
+                .collect::<Result<_, _>>()?,
+            open: &self.open,
+            high: &self.high,
+            low: &self.low,
+            close: &self.close,
+            volume: self.volume.as_deref(),
+            extra: &self.extra,
+        })
+    }
+
+    fn from_blob(blob: Blob) -> Candles {
+        Candles {
+            id: blob.id.into(),
+            open: blob.open,
+            high: blob.high,
+            low: blob.low,
+            close: blob.close,
+            volume: blob.volume,
+            time: blob
+                .time
+                .into_iter()
+                .map(DateTime::from_timestamp_nanos)
+                .collect(),
+            extra: blob.extra,
+            labels: None,
+            volume_policy: VolumePolicy::Optional,
+            instrument: None,
+        }
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl Candles {
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, BlobError> {
+        let mut bytes = frame(MSGPACK);
+        rmp_serde::encode::write(&mut bytes, &self.to_blob()?).map_err(BlobError::MsgpackEncode)?;
+        Ok(bytes)
+    }
+
+    pub fn from_msgpack(bytes: &[u8]) -> Result<Candles, BlobError> {
+        let payload = unframe(bytes, MSGPACK)?;
+        let blob = rmp_serde::from_slice(payload).map_err(BlobError::MsgpackDecode)?;
+        Ok(Candles::from_blob(blob))
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl Candles {
+    pub fn to_bincode(&self) -> Result<Vec<u8>, BlobError> {
+        let mut bytes = frame(BINCODE);
+        let payload = bincode::serde::encode_to_vec(self.to_blob()?, bincode::config::standard())
+            .map_err(BlobError::BincodeEncode)?;
+        bytes.extend_from_slice(&payload);
+        Ok(bytes)
+    }
+
+    pub fn from_bincode(bytes: &[u8]) -> Result<Candles, BlobError> {
+        let payload = unframe(bytes, BINCODE)?;
+        let (blob, _) = bincode::serde::decode_from_slice(payload, bincode::config::standard())
+            .map_err(BlobError::BincodeDecode)?;
+        Ok(Candles::from_blob(blob))
+    }
+}
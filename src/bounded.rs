@@ -0,0 +1,100 @@
+//! Series with a fixed memory footprint for live processes. Bars live in
+//! buffers of twice the bound and the visible window slides over them, so
+//! each push is amortized O(1), columns stay contiguous and the buffers are
+//! never reallocated once full.
+
+use crate::{
+    candle::{Candle, CandleRef, Candles, PushError, UpsertResult},
+    slice::CandlesSlice,
+    symbol::Symbol,
+};
+
+#[derive(Clone, Debug)]
+pub struct BoundedCandles {
+    candles: Candles,
+    start: usize,
+    max_len: usize,
+}
+
+impl BoundedCandles {
+    /// A `max_len` of zero is treated as one.
+    pub fn new(id: impl Into<Symbol>, max_len: usize, has_volume: bool) -> Self {
+        let max_len = max_len.max(1);
+        BoundedCandles {
+            candles: Candles::with_capacity(id, 2 * max_len, has_volume),
+            start: 0,
+            max_len,
+        }
+    }
+
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    pub fn len(&self) -> usize {
+        self.candles.time.len() - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Like [`Candles::try_push`], evicting the oldest bar when full.
+    pub fn try_push(&mut self, candle: Candle) -> Result<(), PushError> {
+        self.candles.try_push(candle)?;
+        self.evict();
+        Ok(())
+    }
+
+    /// Like [`Candles::upsert`], evicting the oldest bar when an append
+    /// overflows.
+    pub fn upsert(&mut self, candle: Candle) -> UpsertResult {
+        let upserted = self.candles.upsert(candle)?;
+        self.evict();
+        Ok(upserted)
+    }
+
+    fn evict(&mut self) {
+        if self.len() > self.max_len {
+            self.start += 1;
+        }
+        if self.start == self.max_len {
+            self.candles.remove_first(self.start);
+            self.start = 0;
+        }
+    }
+
+    pub fn as_slice(&self) -> CandlesSlice<'_> {
+        self.candles.view(self.start..self.candles.time.len())
+    }
+
+    pub fn last(&self) -> Option<CandleRef<'_>> {
+        let slice = self.as_slice();
+        slice.at(slice.len().checked_sub(1)?)
+    }
+
+    pub fn to_candles(&self) -> Candles {
+        self.as_slice().to_candles()
+    }
+
+    pub fn into_candles(mut self) -> Candles {
+        self.candles.remove_first(self.start);
+        self.candles
+    }
+}
+
+impl Candles {
+    /// Keeps the last `max_len` bars and bounds the series to them from now
+    /// on.
+    pub fn with_max_len(mut self, max_len: usize) -> BoundedCandles {
+        let max_len = max_len.max(1);
+        let len = self.time.len();
+        self.remove_first(len.saturating_sub(max_len));
+        self.reserve((2 * max_len).saturating_sub(self.time.len()));
+        BoundedCandles {
+            candles: self,
+            start: 0,
+            max_len,
+        }
+    }
+}
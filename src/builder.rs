@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+
+use crate::{
+    candle::Candles, error::Result, instrument::InstrumentSpec, symbol::Symbol,
+    timeframe::Timeframe, validation::ValidationError,
+};
+
+/// Collects bars without checking them and validates the whole series in
+/// [`CandlesBuilder::build`], so every problem is reported at once.
+#[derive(Clone, Debug)]
+pub struct CandlesBuilder {
+    candles: Candles,
+    timeframe: Option<Timeframe>,
+    unexpected_volume: Vec<usize>,
+}
+
+impl CandlesBuilder {
+    pub fn new(id: impl Into<Symbol>) -> Self {
+        CandlesBuilder {
+            candles: Candles::with_capacity(id, 0, false),
+            timeframe: None,
+            unexpected_volume: Vec::new(),
+        }
+    }
+
+    /// Also requires every bar to open on a `timeframe` boundary.
+    pub fn timeframe(mut self, timeframe: Timeframe) -> Self {
+        self.timeframe = Some(timeframe);
+        self
+    }
+
+    pub fn instrument(mut self, spec: InstrumentSpec) -> Self {
+        self.candles.instrument = Some(spec);
+        self
+    }
+
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.candles.reserve(capacity);
+        self
+    }
+
+    /// Bars pushed before a volume column is added get NaN volume.
+    pub fn with_volume(mut self, has_volume: bool) -> Self {
+        let len = self.candles.time.len();
+        match (has_volume, &self.candles.volume) {
+            (true, None) => {
+                let mut volume = Vec::with_capacity(self.candles.time.capacity());
+                volume.resize(len, f64::NAN);
+                self.candles.volume = Some(volume);
+            }
+            (false, Some(_)) => self.candles.volume = None,
+            _ => {}
+        }
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.candles.time.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.candles.time.is_empty()
+    }
+
+    /// A missing volume is recorded as NaN and fails validation when the
+    /// series has a volume column.
+    pub fn push_bar(
+        &mut self,
+        time: DateTime<Utc>,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: Option<f64>,
+    ) -> &mut Self {
+        if volume.is_some() && self.candles.volume.is_none() {
+            self.unexpected_volume.push(self.candles.time.len());
+        }
+        self.candles.push_row(open, high, low, close, volume, time);
+        self
+    }
+
+    /// Fails with [`ErfurtError::Invalid`](crate::error::ErfurtError::Invalid)
+    /// listing every problem found by [`Candles::validate`] plus misaligned
+    /// bars and volume pushed without a volume column.
+    pub fn build(self) -> Result<Candles> {
+        let mut errors = self.candles.validate().err().unwrap_or_default();
+        errors.extend(
+            self.unexpected_volume
+                .into_iter()
+                .map(|index| ValidationError::UnexpectedVolume { index }),
+        );
+        if let Some(timeframe) = self.timeframe {
+            errors.extend(
+                self.candles
+                    .time
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, time)| !timeframe.is_aligned(**time))
+                    .map(|(index, _)| ValidationError::Misaligned { index }),
+            );
+        }
+        if errors.is_empty() {
+            Ok(self.candles)
+        } else {
+            Err(errors.into())
+        }
+    }
+}
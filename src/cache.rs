@@ -0,0 +1,93 @@
+//! Shared series for multi-symbol live processes. Readers take `Arc`
+//! snapshots that later writes never change: a write while a snapshot is
+//! held copies that series once, so hold snapshots briefly on hot paths.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+use crate::{
+    candle::{Candle, Candles, UpsertResult},
+    symbol::Symbol,
+    timeframe::Timeframe,
+};
+
+pub type CacheKey = (Symbol, Timeframe);
+
+#[derive(Debug, Default)]
+pub struct CandleCache {
+    series: RwLock<HashMap<CacheKey, Arc<Candles>>>,
+}
+
+impl CandleCache {
+    pub fn new() -> Self {
+        CandleCache::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.read(HashMap::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&HashMap<CacheKey, Arc<Candles>>) -> T) -> T {
+        f(&self.series.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn write<T>(&self, f: impl FnOnce(&mut HashMap<CacheKey, Arc<Candles>>) -> T) -> T {
+        f(&mut self.series.write().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    pub fn keys(&self) -> Vec<CacheKey> {
+        self.read(|series| series.keys().cloned().collect())
+    }
+
+    /// Snapshot of the series.
+    pub fn get(&self, symbol: &Symbol, timeframe: Timeframe) -> Option<Arc<Candles>> {
+        self.read(|series| series.get(&(symbol.clone(), timeframe)).cloned())
+    }
+
+    /// Stores `candles` under its own id, returning the series it replaced.
+    pub fn insert(&self, timeframe: Timeframe, candles: Candles) -> Option<Arc<Candles>> {
+        self.write(|series| series.insert((candles.id.clone(), timeframe), Arc::new(candles)))
+    }
+
+    pub fn remove(&self, symbol: &Symbol, timeframe: Timeframe) -> Option<Arc<Candles>> {
+        self.write(|series| series.remove(&(symbol.clone(), timeframe)))
+    }
+
+    /// Snapshot of the series, calling `fetch` without holding the lock when
+    /// it is missing. When two threads fetch the same series at once, the
+    /// first to finish wins and both get its result.
+    pub fn get_or_fetch<E>(
+        &self,
+        symbol: &Symbol,
+        timeframe: Timeframe,
+        fetch: impl FnOnce() -> Result<Candles, E>,
+    ) -> Result<Arc<Candles>, E> {
+        if let Some(candles) = self.get(symbol, timeframe) {
+            return Ok(candles);
+        }
+        let fetched = Arc::new(fetch()?);
+        Ok(self.write(|series| {
+            series
+                .entry((symbol.clone(), timeframe))
+                .or_insert(fetched)
+                .clone()
+        }))
+    }
+
+    /// Upserts a live candle into the series of its id, creating the series
+    /// on first use.
+    pub fn upsert(&self, timeframe: Timeframe, candle: Candle) -> UpsertResult {
+        self.write(|series| {
+            let candles = series
+                .entry((candle.id.clone(), timeframe))
+                .or_insert_with(|| Arc::new(Candles::with_capacity(candle.id.clone(), 0, false)));
+            Arc::make_mut(candles).upsert(candle)
+        })
+    }
+}
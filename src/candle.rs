@@ -1,32 +1,215 @@
+use std::{fmt, iter::FusedIterator, ops::Range};
+
 use chrono::{DateTime, Utc};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    error::ErfurtError,
+    extra::{self, ExtraColumns},
+    field::Field,
+    instrument::InstrumentSpec,
+    slice::CandlesSlice,
+    symbol::Symbol,
+};
+
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Candle {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub volume: Option<f64>,
+    pub time: DateTime<Utc>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Symbol::is_empty")
+    )]
+    pub id: Symbol,
+}
+
+/// Borrowed row of a series.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CandleRef<'a> {
     pub open: f64,
     pub high: f64,
     pub low: f64,
     pub close: f64,
     pub volume: Option<f64>,
     pub time: DateTime<Utc>,
-    pub id: String,
+    pub id: &'a Symbol,
+}
+
+impl CandleRef<'_> {
+    pub fn to_candle(&self) -> Candle {
+        Candle {
+            open: self.open,
+            high: self.high,
+            low: self.low,
+            close: self.close,
+            volume: self.volume,
+            time: self.time,
+            id: self.id.clone(),
+        }
+    }
+}
+
+/// Whether a series carries volume, consulted by pushes, edits, merges and
+/// validation. Code that cannot do without volume can call
+/// [`Candles::require_volume`] instead of unwrapping the column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VolumePolicy {
+    /// The series has a volume column and every pushed bar must fill it.
+    Required,
+    /// The series may or may not have a volume column, and pushed bars must
+    /// match it.
+    #[default]
+    Optional,
+    /// The series never has a volume column; volume of pushed and merged
+    /// bars is discarded.
+    Absent,
+}
+
+impl VolumePolicy {
+    pub fn is_optional(&self) -> bool {
+        *self == VolumePolicy::Optional
+    }
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+/// Equality is exact, so a NaN anywhere makes a series unequal to itself;
+/// see [`Candles::approx_eq`] for float tolerance. Serializes with a schema
+/// version; see [`crate::schema`].
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct Candles {
-    pub id: String,
+    pub id: Symbol,
     pub open: Vec<f64>,
     pub high: Vec<f64>,
     pub low: Vec<f64>,
     pub close: Vec<f64>,
     pub volume: Option<Vec<f64>>,
     pub time: Vec<DateTime<Utc>>,
+    pub extra: ExtraColumns,
+    /// Per-bar tags such as event markers, quality flags or ML classes, zero
+    /// for untagged bars. Kept aligned through pushes, slices and resampling.
+    pub labels: Option<Vec<u32>>,
+    pub volume_policy: VolumePolicy,
+    /// Tick and lot sizes used by [`Candles::round_to_tick`] and for display.
+    pub instrument: Option<InstrumentSpec>,
+}
+
+/// What an empty series takes on from its first bar.
+pub(crate) struct Adopt {
+    pub(crate) id: bool,
+    pub(crate) volume: bool,
+}
+
+/// Checks a push onto a series with `id`, a volume column when `has_volume`
+/// and `last` as its newest time, before anything is changed. An empty series
+/// adopts the bar's id when it has none and a volume column when the bar has
+/// volume; the checks run against what it would adopt.
+pub(crate) fn check_push(
+    id: &Symbol,
+    has_volume: bool,
+    last: Option<DateTime<Utc>>,
+    candle_id: &Symbol,
+    time: DateTime<Utc>,
+    candle_volume: bool,
+) -> Result<Adopt, PushError> {
+    let adopt = Adopt {
+        id: last.is_none() && id.is_empty(),
+        volume: last.is_none() && !has_volume && candle_volume,
+    };
+    if !adopt.id && candle_id != id {
+        return Err(PushError::IdMismatch {
+            expected: id.clone(),
+            found: candle_id.clone(),
+        });
+    }
+    if let Some(last) = last {
+        if time <= last {
+            return Err(PushError::NonIncreasingTime { last, time });
+        }
+    }
+    let has_volume = has_volume || adopt.volume;
+    if candle_volume != has_volume {
+        return Err(PushError::VolumeMismatch {
+            expected: has_volume,
+        });
+    }
+    Ok(adopt)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PushError {
+    IdMismatch {
+        expected: Symbol,
+        found: Symbol,
+    },
+    NonIncreasingTime {
+        last: DateTime<Utc>,
+        time: DateTime<Utc>,
+    },
+    VolumeMismatch {
+        expected: bool,
+    },
+}
+
+impl fmt::Display for PushError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PushError::IdMismatch { expected, found } => {
+                write!(f, "candle id {found} does not match series id {expected}")
+            }
+            PushError::NonIncreasingTime { last, time } => {
+                write!(f, "candle time {time} is not after last time {last}")
+            }
+            PushError::VolumeMismatch { expected: true } => {
+                write!(f, "candle has no volume but series has a volume column")
+            }
+            PushError::VolumeMismatch { expected: false } => {
+                write!(f, "candle has volume but series has no volume column")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PushError {}
+
+#[derive(Clone, Debug)]
+pub enum Upsert {
+    Appended,
+    /// Holds the bar that was replaced.
+    Replaced(Candle),
 }
 
+pub type UpsertResult = Result<Upsert, PushError>;
+
 #[derive(Clone, Debug)]
 pub struct CandlesIterator {
     candles: Candles,
     idx: usize,
+    end: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Iter<'a> {
+    slice: CandlesSlice<'a>,
+    idx: usize,
+    end: usize,
+}
+
+/// Iterator over borrowed rows, see [`Candles::rows`].
+#[derive(Clone, Debug)]
+pub struct Rows<'a> {
+    slice: CandlesSlice<'a>,
+    idx: usize,
+    end: usize,
 }
 
 pub trait CandleExt {
@@ -38,6 +221,32 @@ pub trait CandleExt {
     fn time(&self) -> DateTime<Utc>;
 }
 
+impl CandleExt for CandleRef<'_> {
+    fn open(&self) -> f64 {
+        self.open
+    }
+
+    fn high(&self) -> f64 {
+        self.high
+    }
+
+    fn low(&self) -> f64 {
+        self.low
+    }
+
+    fn close(&self) -> f64 {
+        self.close
+    }
+
+    fn volume(&self) -> Option<f64> {
+        self.volume
+    }
+
+    fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+}
+
 impl CandleExt for Candle {
     fn open(&self) -> f64 {
         self.open
@@ -66,39 +275,67 @@ impl CandleExt for Candle {
 
 pub trait CandlesExt {
     fn get(&self, index: usize) -> Option<Candle>;
-    fn open(&self) -> &Vec<f64>;
-    fn high(&self) -> &Vec<f64>;
-    fn low(&self) -> &Vec<f64>;
-    fn close(&self) -> &Vec<f64>;
-    fn volume(&self) -> &Option<Vec<f64>>;
-    fn time(&self) -> &Vec<DateTime<Utc>>;
+    fn open(&self) -> &[f64];
+    fn high(&self) -> &[f64];
+    fn low(&self) -> &[f64];
+    fn close(&self) -> &[f64];
+    fn volume(&self) -> Option<&[f64]>;
+    fn time(&self) -> &[DateTime<Utc>];
     fn last(&self) -> Option<Candle>;
     fn take_last(&self, n: usize) -> Option<Candles>;
+
+    /// The column of `field`, empty for volume without a volume column.
+    fn column(&self, field: Field) -> &[f64] {
+        match field {
+            Field::Open => self.open(),
+            Field::High => self.high(),
+            Field::Low => self.low(),
+            Field::Close => self.close(),
+            Field::Volume => self.volume().unwrap_or_default(),
+        }
+    }
+
+    fn extra(&self, _name: &str) -> Option<&[f64]> {
+        None
+    }
+
+    fn trades(&self) -> Option<&[f64]> {
+        self.extra(extra::TRADES)
+    }
+
+    fn quote_volume(&self) -> Option<&[f64]> {
+        self.extra(extra::QUOTE_VOLUME)
+    }
+
+    fn open_interest(&self) -> Option<&[f64]> {
+        self.extra(extra::OPEN_INTEREST)
+    }
+
+    fn bid_close(&self) -> Option<&[f64]> {
+        self.extra(extra::BID_CLOSE)
+    }
+
+    fn ask_close(&self) -> Option<&[f64]> {
+        self.extra(extra::ASK_CLOSE)
+    }
+
+    fn buy_volume(&self) -> Option<&[f64]> {
+        self.extra(extra::BUY_VOLUME)
+    }
+
+    fn sell_volume(&self) -> Option<&[f64]> {
+        self.extra(extra::SELL_VOLUME)
+    }
+
+    fn cumulative_delta(&self) -> Option<&[f64]> {
+        self.extra(extra::CUMULATIVE_DELTA)
+    }
 }
 
 impl CandlesExt for Candles {
+    /// `None` also when a column is too short; see [`Candles::try_get`].
     fn get(&self, index: usize) -> Option<Candle> {
-        if index < self.time.len() {
-            let symbol = &self.id;
-            let open = self.open[index];
-            let high = self.high[index];
-            let low = self.low[index];
-            let close = self.close[index];
-            let volume = self.volume.as_ref().map(|x| x[index]);
-            let time = self.time[index];
-
-            Some(Candle {
-                id: symbol.clone(),
-                open,
-                high,
-                low,
-                close,
-                volume,
-                time,
-            })
-        } else {
-            None
-        }
+        self.try_get(index).ok()
     }
 
     fn last(&self) -> Option<Candle> {
@@ -114,49 +351,46 @@ impl CandlesExt for Candles {
     }
 
     #[inline]
-    fn open(&self) -> &Vec<f64> {
+    fn open(&self) -> &[f64] {
         &self.open
     }
 
     #[inline]
-    fn high(&self) -> &Vec<f64> {
+    fn high(&self) -> &[f64] {
         &self.high
     }
 
     #[inline]
-    fn low(&self) -> &Vec<f64> {
+    fn low(&self) -> &[f64] {
         &self.low
     }
 
     #[inline]
-    fn close(&self) -> &Vec<f64> {
+    fn close(&self) -> &[f64] {
         &self.close
     }
 
     #[inline]
-    fn volume(&self) -> &Option<Vec<f64>> {
-        &self.volume
+    fn volume(&self) -> Option<&[f64]> {
+        self.volume.as_deref()
     }
 
     #[inline]
-    fn time(&self) -> &Vec<DateTime<Utc>> {
+    fn time(&self) -> &[DateTime<Utc>] {
         &self.time
     }
 
+    #[inline]
+    fn extra(&self, name: &str) -> Option<&[f64]> {
+        self.extra.get(name)
+    }
+
     fn take_last(&self, n: usize) -> Option<Candles> {
         let len = self.time.len();
         if len < n {
             None
         } else {
-            Some(Candles {
-                id: self.id.clone(),
-                open: self.open[len - n..].to_vec(),
-                high: self.high[len - n..].to_vec(),
-                low: self.low[len - n..].to_vec(),
-                close: self.close[len - n..].to_vec(),
-                volume: self.volume.as_ref().map(|xs| xs[len - n..].to_vec()),
-                time: self.time[len - n..].to_vec(),
-            })
+            Some(self.copy_range(len - n..len))
         }
     }
 }
@@ -168,35 +402,40 @@ impl CandlesExt for &Candles {
     }
 
     #[inline]
-    fn open(&self) -> &Vec<f64> {
+    fn open(&self) -> &[f64] {
         &self.open
     }
 
     #[inline]
-    fn high(&self) -> &Vec<f64> {
+    fn high(&self) -> &[f64] {
         &self.high
     }
 
     #[inline]
-    fn low(&self) -> &Vec<f64> {
+    fn low(&self) -> &[f64] {
         &self.low
     }
 
     #[inline]
-    fn close(&self) -> &Vec<f64> {
+    fn close(&self) -> &[f64] {
         &self.close
     }
 
     #[inline]
-    fn volume(&self) -> &Option<Vec<f64>> {
-        &self.volume
+    fn volume(&self) -> Option<&[f64]> {
+        self.volume.as_deref()
     }
 
     #[inline]
-    fn time(&self) -> &Vec<DateTime<Utc>> {
+    fn time(&self) -> &[DateTime<Utc>] {
         &self.time
     }
 
+    #[inline]
+    fn extra(&self, name: &str) -> Option<&[f64]> {
+        self.extra.get(name)
+    }
+
     #[inline]
     fn last(&self) -> Option<Candle> {
         self.to_owned().last()
@@ -207,23 +446,224 @@ impl CandlesExt for &Candles {
         if len < n {
             None
         } else {
-            Some(Candles {
-                id: self.id.clone(),
-                open: self.open[len - n..].to_vec(),
-                high: self.high[len - n..].to_vec(),
-                low: self.low[len - n..].to_vec(),
-                close: self.close[len - n..].to_vec(),
-                volume: self.volume.as_ref().map(|xs| xs[len - n..].to_vec()),
-                time: self.time[len - n..].to_vec(),
-            })
+            Some(self.copy_range(len - n..len))
         }
     }
 }
 
 impl Candles {
+    /// Like [`CandlesExt::get`], telling an index past the end apart from a
+    /// column that is shorter than the time column.
+    pub fn try_get(&self, index: usize) -> Result<Candle, ErfurtError> {
+        let len = self.time.len();
+        let time = *self
+            .time
+            .get(index)
+            .ok_or(ErfurtError::OutOfBounds { index, len })?;
+        let value = |column: &'static str, xs: &[f64]| {
+            xs.get(index)
+                .copied()
+                .ok_or(ErfurtError::MissingValue { column, index })
+        };
+        Ok(Candle {
+            open: value("open", &self.open)?,
+            high: value("high", &self.high)?,
+            low: value("low", &self.low)?,
+            close: value("close", &self.close)?,
+            volume: match &self.volume {
+                Some(xs) => Some(value("volume", xs)?),
+                None => None,
+            },
+            time,
+            id: self.id.clone(),
+        })
+    }
+
+    /// The volume column, failing with [`ErfurtError::NoVolume`] without one.
+    pub fn require_volume(&self) -> Result<&[f64], ErfurtError> {
+        self.volume.as_deref().ok_or(ErfurtError::NoVolume)
+    }
+
+    /// Switches the policy, dropping the volume column for
+    /// [`VolumePolicy::Absent`] and adding an empty one for
+    /// [`VolumePolicy::Required`] on an empty series. Fails when a non-empty
+    /// series without volume is made `Required`.
+    pub fn set_volume_policy(&mut self, policy: VolumePolicy) -> Result<(), ErfurtError> {
+        match policy {
+            VolumePolicy::Required if self.volume.is_none() => {
+                if !self.is_empty() {
+                    return Err(ErfurtError::NoVolume);
+                }
+                self.volume = Some(Vec::new());
+            }
+            VolumePolicy::Absent => self.volume = None,
+            _ => {}
+        }
+        self.volume_policy = policy;
+        Ok(())
+    }
+
+    pub fn with_capacity(id: impl Into<Symbol>, capacity: usize, has_volume: bool) -> Candles {
+        Candles {
+            id: id.into(),
+            open: Vec::with_capacity(capacity),
+            high: Vec::with_capacity(capacity),
+            low: Vec::with_capacity(capacity),
+            close: Vec::with_capacity(capacity),
+            volume: has_volume.then(|| Vec::with_capacity(capacity)),
+            time: Vec::with_capacity(capacity),
+            ..Default::default()
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.time.capacity()
+    }
+
+    /// Reserves room for `additional` more bars in every column.
+    pub fn reserve(&mut self, additional: usize) {
+        self.for_each_column(|xs| xs.reserve(additional));
+        self.time.reserve(additional);
+        if let Some(xs) = self.labels.as_mut() {
+            xs.reserve(additional);
+        }
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.for_each_column(Vec::shrink_to_fit);
+        self.time.shrink_to_fit();
+        if let Some(xs) = self.labels.as_mut() {
+            xs.shrink_to_fit();
+        }
+    }
+
+    /// Keeps the first `len` bars.
+    pub fn truncate(&mut self, len: usize) {
+        self.for_each_column(|xs| xs.truncate(len));
+        self.time.truncate(len);
+        if let Some(xs) = self.labels.as_mut() {
+            xs.truncate(len);
+        }
+    }
+
+    /// Removes the bars opening before `time` and returns them.
+    pub fn drain_before(&mut self, time: DateTime<Utc>) -> Candles {
+        let end = self.time.partition_point(|&t| t < time);
+        let drained = self.copy_range(0..end);
+        self.remove_first(end);
+        drained
+    }
+
+    /// Drops the first `n` bars in place, keeping the capacity.
+    pub(crate) fn remove_first(&mut self, n: usize) {
+        self.for_each_column(|xs| {
+            xs.drain(..n);
+        });
+        self.time.drain(..n);
+        if let Some(xs) = self.labels.as_mut() {
+            xs.drain(..n);
+        }
+    }
+
+    pub fn retain(&mut self, f: impl FnMut(CandleRef<'_>) -> bool) {
+        let keep = self.rows().map(f).collect::<Vec<_>>();
+        self.for_each_column(|xs| {
+            let mut keep = keep.iter();
+            xs.retain(|_| *keep.next().unwrap());
+        });
+        if let Some(xs) = self.labels.as_mut() {
+            let mut keep = keep.iter();
+            xs.retain(|_| *keep.next().unwrap());
+        }
+        let mut keep = keep.iter();
+        self.time.retain(|_| *keep.next().unwrap());
+    }
+
+    fn for_each_column(&mut self, mut f: impl FnMut(&mut Vec<f64>)) {
+        f(&mut self.open);
+        f(&mut self.high);
+        f(&mut self.low);
+        f(&mut self.close);
+        if let Some(xs) = self.volume.as_mut() {
+            f(xs);
+        }
+        for (_, xs) in self.extra.iter_mut() {
+            f(xs);
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.time.is_empty()
     }
+
+    pub(crate) fn copy_range(&self, range: Range<usize>) -> Candles {
+        Candles {
+            id: self.id.clone(),
+            open: self.open[range.clone()].to_vec(),
+            high: self.high[range.clone()].to_vec(),
+            low: self.low[range.clone()].to_vec(),
+            close: self.close[range.clone()].to_vec(),
+            volume: self.volume.as_ref().map(|xs| xs[range.clone()].to_vec()),
+            time: self.time[range.clone()].to_vec(),
+            extra: self.extra.map(|xs| xs[range.clone()].to_vec()),
+            labels: self.labels.as_ref().map(|xs| xs[range.clone()].to_vec()),
+            volume_policy: self.volume_policy,
+            instrument: self.instrument,
+        }
+    }
+
+    pub(crate) fn empty_like(&self) -> Candles {
+        Candles {
+            id: self.id.clone(),
+            volume: self.volume.as_ref().map(|_| Vec::new()),
+            extra: self.extra.empty_like(),
+            labels: self.labels.as_ref().map(|_| Vec::new()),
+            volume_policy: self.volume_policy,
+            instrument: self.instrument,
+            ..Default::default()
+        }
+    }
+
+    pub fn index_of(&self, time: DateTime<Utc>) -> Option<usize> {
+        self.time.binary_search(&time).ok()
+    }
+
+    pub fn at_or_before(&self, time: DateTime<Utc>) -> Option<Candle> {
+        match self.time.partition_point(|x| *x <= time) {
+            0 => None,
+            n => self.get(n - 1),
+        }
+    }
+
+    /// Bars with `start <= time < end`.
+    pub fn between(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Candles {
+        let from = self.time.partition_point(|time| *time < start);
+        let to = self.time.partition_point(|time| *time < end).max(from);
+        self.copy_range(from..to)
+    }
+
+    /// Bars with `time >= start`.
+    pub fn since(&self, start: DateTime<Utc>) -> Candles {
+        let from = self.time.partition_point(|time| *time < start);
+        self.copy_range(from..self.time.len())
+    }
+
+    /// Bars with `time < end`.
+    pub fn until(&self, end: DateTime<Utc>) -> Candles {
+        let to = self.time.partition_point(|time| *time < end);
+        self.copy_range(0..to)
+    }
+
+    /// Splits into bars before `time` and bars from `time` on.
+    pub fn split_at_time(&self, time: DateTime<Utc>) -> (Candles, Candles) {
+        let mid = self.time.partition_point(|t| *t < time);
+        (
+            self.copy_range(0..mid),
+            self.copy_range(mid..self.time.len()),
+        )
+    }
+
+    #[deprecated(note = "use `try_push`, which validates id, time order and volume")]
     pub fn push(
         &mut self,
         open: f64,
@@ -232,21 +672,177 @@ impl Candles {
         close: f64,
         volume: Option<f64>,
         time: DateTime<Utc>,
+    ) {
+        self.push_row(open, high, low, close, volume, time);
+    }
+
+    pub fn try_push(&mut self, mut candle: Candle) -> Result<(), PushError> {
+        if self.volume_policy == VolumePolicy::Absent {
+            candle.volume = None;
+        }
+        let adopt = check_push(
+            &self.id,
+            self.volume.is_some(),
+            self.time.last().copied(),
+            &candle.id,
+            candle.time,
+            candle.volume.is_some(),
+        )?;
+        if adopt.id {
+            self.id = candle.id.clone();
+        }
+        if adopt.volume {
+            self.volume = Some(Vec::new());
+        }
+        self.push_row(
+            candle.open,
+            candle.high,
+            candle.low,
+            candle.close,
+            candle.volume,
+            candle.time,
+        );
+        Ok(())
+    }
+
+    /// Replaces the last bar when `candle` has the same time, as exchanges do
+    /// while a bar is forming, and appends it otherwise. Extra columns of a
+    /// replaced bar are reset to NaN; its label is kept.
+    pub fn upsert(&mut self, mut candle: Candle) -> UpsertResult {
+        if self.volume_policy == VolumePolicy::Absent {
+            candle.volume = None;
+        }
+        let Some(last) = self.last().filter(|last| last.time == candle.time) else {
+            return self.try_push(candle).map(|_| Upsert::Appended);
+        };
+        if candle.id != self.id {
+            return Err(PushError::IdMismatch {
+                expected: self.id.clone(),
+                found: candle.id,
+            });
+        }
+        self.check_volume(&candle)?;
+        let index = self.time.len() - 1;
+        self.open[index] = candle.open;
+        self.high[index] = candle.high;
+        self.low[index] = candle.low;
+        self.close[index] = candle.close;
+        if let (Some(xs), Some(volume)) = (self.volume.as_mut(), candle.volume) {
+            xs[index] = volume;
+        }
+        for (_, xs) in self.extra.iter_mut() {
+            xs[index] = f64::NAN;
+        }
+        Ok(Upsert::Replaced(last))
+    }
+
+    fn check_volume(&self, candle: &Candle) -> Result<(), PushError> {
+        if candle.volume.is_some() != self.volume.is_some() {
+            return Err(PushError::VolumeMismatch {
+                expected: self.volume.is_some(),
+            });
+        }
+        Ok(())
+    }
+
+    pub(crate) fn push_row(
+        &mut self,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: Option<f64>,
+        time: DateTime<Utc>,
     ) {
         self.open.push(open);
         self.high.push(high);
         self.low.push(low);
         self.close.push(close);
-        if let Some(value) = volume {
-            self.volume.as_mut().unwrap().push(value);
-        };
+        if let Some(xs) = self.volume.as_mut() {
+            xs.push(volume.unwrap_or(f64::NAN));
+        }
         self.time.push(time);
+        for (_, xs) in self.extra.iter_mut() {
+            xs.push(f64::NAN);
+        }
+        if let Some(xs) = self.labels.as_mut() {
+            xs.push(0);
+        }
     }
-    pub fn iter(&self) -> CandlesIterator {
-        CandlesIterator {
-            candles: self.clone(),
-            idx: 0,
+
+    pub(crate) fn gather(&self, indices: &[usize]) -> Candles {
+        Candles {
+            id: self.id.clone(),
+            open: indices.iter().map(|&i| self.open[i]).collect(),
+            high: indices.iter().map(|&i| self.high[i]).collect(),
+            low: indices.iter().map(|&i| self.low[i]).collect(),
+            close: indices.iter().map(|&i| self.close[i]).collect(),
+            volume: self
+                .volume
+                .as_ref()
+                .map(|xs| indices.iter().map(|&i| xs[i]).collect()),
+            time: indices.iter().map(|&i| self.time[i]).collect(),
+            extra: self
+                .extra
+                .map(|xs| indices.iter().map(|&i| xs[i]).collect()),
+            labels: self
+                .labels
+                .as_ref()
+                .map(|xs| indices.iter().map(|&i| xs[i]).collect()),
+            volume_policy: self.volume_policy,
+            instrument: self.instrument,
+        }
+    }
+
+    pub(crate) fn push_index(&mut self, other: &Candles, index: usize) {
+        self.open.push(other.open[index]);
+        self.high.push(other.high[index]);
+        self.low.push(other.low[index]);
+        self.close.push(other.close[index]);
+        if let Some(xs) = self.volume.as_mut() {
+            xs.push(other.volume.as_ref().map_or(f64::NAN, |ys| ys[index]));
         }
+        self.time.push(other.time[index]);
+        for (name, xs) in self.extra.iter_mut() {
+            xs.push(other.extra.get(name).map_or(f64::NAN, |ys| ys[index]));
+        }
+        if let Some(xs) = self.labels.as_mut() {
+            xs.push(other.labels.as_ref().map_or(0, |ys| ys[index]));
+        }
+    }
+
+    pub fn try_extend<I: IntoIterator<Item = Candle>>(&mut self, iter: I) -> Result<(), PushError> {
+        for candle in iter {
+            self.try_push(candle)?;
+        }
+        Ok(())
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        self.as_slice().iter()
+    }
+
+    /// Like [`Candles::iter`] but yields borrowed rows.
+    pub fn rows(&self) -> Rows<'_> {
+        self.as_slice().rows()
+    }
+}
+
+/// Panics if a candle is rejected by [`Candles::try_push`].
+impl Extend<Candle> for Candles {
+    fn extend<I: IntoIterator<Item = Candle>>(&mut self, iter: I) {
+        if let Err(err) = self.try_extend(iter) {
+            panic!("{err}");
+        }
+    }
+}
+
+/// Panics if a candle is rejected by [`Candles::try_push`].
+impl FromIterator<Candle> for Candles {
+    fn from_iter<I: IntoIterator<Item = Candle>>(iter: I) -> Self {
+        let mut candles = Candles::default();
+        candles.extend(iter);
+        candles
     }
 }
 
@@ -256,9 +852,11 @@ impl IntoIterator for Candles {
     type IntoIter = CandlesIterator;
 
     fn into_iter(self) -> Self::IntoIter {
+        let end = self.time.len();
         CandlesIterator {
             candles: self,
             idx: 0,
+            end,
         }
     }
 }
@@ -266,8 +864,142 @@ impl IntoIterator for Candles {
 impl Iterator for CandlesIterator {
     type Item = Candle;
     fn next(&mut self) -> Option<Self::Item> {
-        let candle = self.candles.get(self.idx);
-        self.idx += 1;
-        candle
+        if self.idx < self.end {
+            let candle = self.candles.get(self.idx);
+            self.idx += 1;
+            candle
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.idx;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for CandlesIterator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.end -= 1;
+            self.candles.get(self.end)
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for CandlesIterator {}
+
+impl FusedIterator for CandlesIterator {}
+
+impl<'a> CandlesSlice<'a> {
+    pub fn iter(&self) -> Iter<'a> {
+        Iter {
+            slice: *self,
+            idx: 0,
+            end: self.len(),
+        }
+    }
+
+    pub fn rows(&self) -> Rows<'a> {
+        Rows {
+            slice: *self,
+            idx: 0,
+            end: self.len(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Candles {
+    type Item = Candle;
+
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for CandlesSlice<'a> {
+    type Item = Candle;
+
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Iterator for Iter<'_> {
+    type Item = Candle;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            let candle = self.slice.get(self.idx);
+            self.idx += 1;
+            candle
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.idx;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.end -= 1;
+            self.slice.get(self.end)
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl FusedIterator for Iter<'_> {}
+
+impl<'a> Iterator for Rows<'a> {
+    type Item = CandleRef<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            let candle = self.slice.at(self.idx);
+            self.idx += 1;
+            candle
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.idx;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Rows<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx < self.end {
+            self.end -= 1;
+            self.slice.at(self.end)
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for Rows<'_> {}
+
+impl FusedIterator for Rows<'_> {}
+
+impl From<CandleRef<'_>> for Candle {
+    fn from(candle: CandleRef<'_>) -> Self {
+        candle.to_candle()
     }
 }
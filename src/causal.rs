@@ -0,0 +1,132 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    candle::{CandleRef, Candles},
+    index::CandlesIndex,
+    slice::CandlesSlice,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CausalError {
+    /// The request reaches bars stamped after the cursor.
+    Lookahead { cursor: DateTime<Utc> },
+    /// The request is out of bounds of the whole series too.
+    OutOfBounds,
+    /// The cursor only moves forward.
+    Backwards {
+        cursor: DateTime<Utc>,
+        to: DateTime<Utc>,
+    },
+}
+
+impl fmt::Display for CausalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CausalError::Lookahead { cursor } => {
+                write!(f, "lookahead past cursor {cursor}")
+            }
+            CausalError::OutOfBounds => write!(f, "index out of bounds"),
+            CausalError::Backwards { cursor, to } => {
+                write!(f, "cannot move cursor back from {cursor} to {to}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CausalError {}
+
+/// Series guarded by a cursor: only bars stamped at or before the cursor
+/// are visible, and reaching past them is an error instead of silent
+/// lookahead. Bars are stamped with their open time, so advance to a bar's
+/// close before treating it as complete.
+#[derive(Clone, Copy, Debug)]
+pub struct CausalCandles<'a> {
+    candles: CandlesSlice<'a>,
+    cursor: DateTime<Utc>,
+    visible: usize,
+}
+
+impl<'a> CausalCandles<'a> {
+    pub fn new(candles: CandlesSlice<'a>, cursor: DateTime<Utc>) -> Self {
+        CausalCandles {
+            candles,
+            cursor,
+            visible: candles.time.partition_point(|time| *time <= cursor),
+        }
+    }
+
+    pub fn cursor(&self) -> DateTime<Utc> {
+        self.cursor
+    }
+
+    /// Number of visible bars.
+    pub fn len(&self) -> usize {
+        self.visible
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.visible == 0
+    }
+
+    /// True once every bar is visible.
+    pub fn is_exhausted(&self) -> bool {
+        self.visible == self.candles.len()
+    }
+
+    pub fn advance(&mut self, to: DateTime<Utc>) -> Result<(), CausalError> {
+        if to < self.cursor {
+            return Err(CausalError::Backwards {
+                cursor: self.cursor,
+                to,
+            });
+        }
+        self.cursor = to;
+        self.visible += self.candles.time[self.visible..].partition_point(|time| *time <= to);
+        Ok(())
+    }
+
+    /// Moves the cursor to the next bar, returning it.
+    pub fn step(&mut self) -> Option<CandleRef<'a>> {
+        let time = *self.candles.time.get(self.visible)?;
+        self.cursor = time;
+        self.visible += 1;
+        self.last()
+    }
+
+    /// The visible bars.
+    pub fn view(&self) -> CandlesSlice<'a> {
+        self.candles.slice(0..self.visible).unwrap()
+    }
+
+    pub fn last(&self) -> Option<CandleRef<'a>> {
+        self.view().at(self.visible.checked_sub(1)?)
+    }
+
+    /// Like [`CandlesSlice::at`], failing when `index` reaches past the
+    /// cursor.
+    pub fn at<I: CandlesIndex + Clone>(&self, index: I) -> Result<I::Output<'a>, CausalError> {
+        match index.clone().index(self.view()) {
+            Some(output) => Ok(output),
+            None if index.index(self.candles).is_some() => Err(CausalError::Lookahead {
+                cursor: self.cursor,
+            }),
+            None => Err(CausalError::OutOfBounds),
+        }
+    }
+
+    /// The last `n` visible bars, failing when fewer are visible.
+    pub fn tail(&self, n: usize) -> Result<CandlesSlice<'a>, CausalError> {
+        self.visible
+            .checked_sub(n)
+            .and_then(|start| self.candles.slice(start..self.visible))
+            .ok_or(CausalError::OutOfBounds)
+    }
+}
+
+impl Candles {
+    pub fn causal(&self, cursor: DateTime<Utc>) -> CausalCandles<'_> {
+        CausalCandles::new(self.as_slice(), cursor)
+    }
+}
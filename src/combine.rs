@@ -0,0 +1,80 @@
+use crate::{candle::Candles, map::Align, symbol::Symbol};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CombineOp {
+    /// `self - other`, e.g. a calendar spread.
+    Subtract,
+    /// `self / other`, e.g. a pair ratio; assumes positive prices.
+    Ratio,
+}
+
+impl CombineOp {
+    fn apply(self, a: f64, b: f64) -> f64 {
+        match self {
+            CombineOp::Subtract => a - b,
+            CombineOp::Ratio => a / b,
+        }
+    }
+
+    fn id(self, a: &Symbol, b: &Symbol) -> Symbol {
+        match self {
+            CombineOp::Subtract => format!("{a}-{b}").into(),
+            CombineOp::Ratio => format!("{a}/{b}").into(),
+        }
+    }
+}
+
+impl Candles {
+    /// Synthetic instrument from two sorted legs, with the id `a-b` or `a/b`.
+    /// Open and close combine the legs' opens and closes. High and low are
+    /// the widest values the legs allow, this high against the other low and
+    /// vice versa, widened to contain open and close; without ticks a bar
+    /// cannot tell whether both extremes were reached together. With
+    /// [`Align::Outer`] a leg missing a bar is carried as a flat bar at its
+    /// previous close, and times before both legs have traded are skipped.
+    /// The result has no volume.
+    pub fn combine(&self, other: &Candles, op: CombineOp, align: Align) -> Candles {
+        let mut combined = Candles {
+            id: op.id(&self.id, &other.id),
+            ..Default::default()
+        };
+        let leg = |candles: &Candles, next: usize, here: bool| {
+            let k = next.checked_sub(1)?;
+            Some(if here {
+                [
+                    candles.open[k],
+                    candles.high[k],
+                    candles.low[k],
+                    candles.close[k],
+                ]
+            } else {
+                [candles.close[k]; 4]
+            })
+        };
+        let (mut i, mut j) = (0, 0);
+        loop {
+            let time = match (self.time.get(i), other.time.get(j)) {
+                (Some(&a), Some(&b)) => a.min(b),
+                (Some(&a), None) => a,
+                (None, Some(&b)) => b,
+                (None, None) => break,
+            };
+            let a_here = self.time.get(i) == Some(&time);
+            let b_here = other.time.get(j) == Some(&time);
+            i += usize::from(a_here);
+            j += usize::from(b_here);
+            if align == Align::Inner && !(a_here && b_here) {
+                continue;
+            }
+            let (Some(a), Some(b)) = (leg(self, i, a_here), leg(other, j, b_here)) else {
+                continue;
+            };
+            let open = op.apply(a[0], b[0]);
+            let close = op.apply(a[3], b[3]);
+            let high = op.apply(a[1], b[2]).max(open).max(close);
+            let low = op.apply(a[2], b[1]).min(open).min(close);
+            combined.push_row(open, high, low, close, None, time);
+        }
+        combined
+    }
+}
@@ -0,0 +1,249 @@
+//! Compressed column files. Little-endian layout: 24 byte header (magic,
+//! version, codec, bar count), the id as a u64 length and bytes, a u64
+//! column count, then per column a kind byte (0 for the fixed columns in
+//! the order time, open, high, low, close and volume, 1 for extras), the
+//! name as a u64 length and bytes, and the compressed payload as a u64
+//! length and bytes. Payloads are the 8 byte values of the column
+//! byte-shuffled, all first bytes then all second bytes and so on, which
+//! lines up the slowly changing sign and exponent bytes for the compressor.
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Read, Write},
+    path::Path,
+};
+
+use chrono::{DateTime, Utc};
+
+use crate::{candle::Candles, extra::ExtraColumns};
+
+const MAGIC: &[u8; 8] = b"ERFURTZ\0";
+const VERSION: u32 = 1;
+const HEADER_LEN: usize = 24;
+const FIXED: u8 = 0;
+const EXTRA: u8 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Codec {
+    /// Shuffled but uncompressed.
+    None,
+    /// Zstandard at `level`, 1 to 22; 3 is the library default.
+    Zstd { level: i32 },
+}
+
+impl Codec {
+    fn tag(&self) -> u32 {
+        match self {
+            Codec::None => 0,
+            Codec::Zstd { .. } => 1,
+        }
+    }
+
+    fn compress(&self, bytes: &[u8]) -> io::Result<Vec<u8>> {
+        match *self {
+            Codec::None => Ok(bytes.to_vec()),
+            Codec::Zstd { level } => zstd::bulk::compress(bytes, level),
+        }
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn shuffle(values: impl ExactSizeIterator<Item = [u8; 8]>) -> Vec<u8> {
+    let len = values.len();
+    let mut bytes = vec![0; len * 8];
+    for (i, value) in values.enumerate() {
+        for (j, byte) in value.into_iter().enumerate() {
+            bytes[j * len + i] = byte;
+        }
+    }
+    bytes
+}
+
+fn unshuffle(bytes: &[u8]) -> Vec<[u8; 8]> {
+    let len = bytes.len() / 8;
+    (0..len)
+        .map(|i| std::array::from_fn(|j| bytes[j * len + i]))
+        .collect()
+}
+
+fn write_bytes<W: Write>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
+    writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    writer.write_all(bytes)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    at: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        let end = self
+            .at
+            .checked_add(len)
+            .ok_or_else(|| invalid("length overflow"))?;
+        let bytes = self
+            .bytes
+            .get(self.at..end)
+            .ok_or_else(|| invalid("truncated compressed file"))?;
+        self.at = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> io::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u64(&mut self) -> io::Result<usize> {
+        let value = u64::from_le_bytes(self.take(8)?.try_into().unwrap());
+        usize::try_from(value).map_err(|_| invalid("length overflow"))
+    }
+
+    fn bytes(&mut self) -> io::Result<&'a [u8]> {
+        let len = self.u64()?;
+        self.take(len)
+    }
+}
+
+fn encode<W: Write>(candles: &Candles, codec: Codec, mut writer: W) -> io::Result<()> {
+    let nanos = candles
+        .time
+        .iter()
+        .map(|time| {
+            time.timestamp_nanos_opt().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "time out of the nanosecond range",
+                )
+            })
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+    let f64s = |xs: &[f64]| shuffle(xs.iter().map(|x| x.to_le_bytes()));
+    let mut columns = vec![
+        (
+            FIXED,
+            "time",
+            shuffle(nanos.iter().map(|x| x.to_le_bytes())),
+        ),
+        (FIXED, "open", f64s(&candles.open)),
+        (FIXED, "high", f64s(&candles.high)),
+        (FIXED, "low", f64s(&candles.low)),
+        (FIXED, "close", f64s(&candles.close)),
+    ];
+    if let Some(volume) = &candles.volume {
+        columns.push((FIXED, "volume", f64s(volume)));
+    }
+    columns.extend(
+        candles
+            .extra
+            .iter()
+            .map(|(name, xs)| (EXTRA, name, f64s(xs))),
+    );
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&codec.tag().to_le_bytes())?;
+    writer.write_all(&(candles.time.len() as u64).to_le_bytes())?;
+    write_bytes(&mut writer, candles.id.as_bytes())?;
+    writer.write_all(&(columns.len() as u64).to_le_bytes())?;
+    for (kind, name, bytes) in columns {
+        writer.write_all(&[kind])?;
+        write_bytes(&mut writer, name.as_bytes())?;
+        write_bytes(&mut writer, &codec.compress(&bytes)?)?;
+    }
+    writer.flush()
+}
+
+/// Streams `payload` through the decoder, reading at most one byte past
+/// `limit`, so the bar count of a corrupt header cannot size the buffer.
+fn decompress(payload: &[u8], limit: usize) -> io::Result<Vec<u8>> {
+    let mut raw = Vec::new();
+    zstd::stream::read::Decoder::new(payload)?
+        .take(limit as u64 + 1)
+        .read_to_end(&mut raw)?;
+    Ok(raw)
+}
+
+fn decode(bytes: &[u8]) -> io::Result<Candles> {
+    if bytes.len() < HEADER_LEN || &bytes[..8] != MAGIC {
+        return Err(invalid("not an erfurt compressed file"));
+    }
+    let u32_at = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+    if u32_at(8) != VERSION {
+        return Err(invalid("unsupported erfurt compressed version"));
+    }
+    let codec = u32_at(12);
+    let mut reader = Reader {
+        bytes,
+        at: HEADER_LEN - 8,
+    };
+    let len = reader.u64()?;
+    let column_len = len
+        .checked_mul(8)
+        .ok_or_else(|| invalid("bar count overflow"))?;
+    let id = std::str::from_utf8(reader.bytes()?).map_err(|_| invalid("id is not utf-8"))?;
+    let mut candles = Candles {
+        id: id.into(),
+        ..Default::default()
+    };
+    let mut extra = ExtraColumns::new();
+    for _ in 0..reader.u64()? {
+        let kind = reader.u8()?;
+        let name = std::str::from_utf8(reader.bytes()?)
+            .map_err(|_| invalid("column name is not utf-8"))?
+            .to_string();
+        let payload = reader.bytes()?;
+        let raw = match codec {
+            0 => payload.to_vec(),
+            1 => decompress(payload, column_len)?,
+            _ => return Err(invalid("unknown codec")),
+        };
+        if raw.len() != column_len {
+            return Err(invalid("column length does not match bar count"));
+        }
+        let values = unshuffle(&raw);
+        let f64s = || values.iter().map(|x| f64::from_le_bytes(*x)).collect();
+        match (kind, name.as_str()) {
+            (FIXED, "time") => {
+                candles.time = values
+                    .iter()
+                    .map(|x| DateTime::<Utc>::from_timestamp_nanos(i64::from_le_bytes(*x)))
+                    .collect()
+            }
+            (FIXED, "open") => candles.open = f64s(),
+            (FIXED, "high") => candles.high = f64s(),
+            (FIXED, "low") => candles.low = f64s(),
+            (FIXED, "close") => candles.close = f64s(),
+            (FIXED, "volume") => candles.volume = Some(f64s()),
+            (EXTRA, _) => {
+                extra.insert(name, f64s());
+            }
+            _ => return Err(invalid("unknown column")),
+        }
+    }
+    let fixed = [
+        candles.time.len(),
+        candles.open.len(),
+        candles.high.len(),
+        candles.low.len(),
+        candles.close.len(),
+    ];
+    if fixed.iter().any(|&n| n != len) {
+        return Err(invalid("missing time or price column"));
+    }
+    candles.extra = extra;
+    Ok(candles)
+}
+
+impl Candles {
+    pub fn save_compressed<P: AsRef<Path>>(&self, path: P, codec: Codec) -> io::Result<()> {
+        encode(self, codec, BufWriter::new(File::create(path)?))
+    }
+
+    pub fn load_compressed<P: AsRef<Path>>(path: P) -> io::Result<Candles> {
+        decode(&std::fs::read(path)?)
+    }
+}
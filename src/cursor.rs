@@ -0,0 +1,86 @@
+use chrono::{DateTime, Utc};
+
+use crate::{
+    candle::{CandleRef, Candles},
+    slice::CandlesSlice,
+};
+
+/// Position in a borrowed series for strategy loops: [`Iterator::next`]
+/// consumes the next bar, which becomes the current one, and
+/// [`CandlesCursor::history`] looks back from it.
+#[derive(Clone, Copy, Debug)]
+pub struct CandlesCursor<'a> {
+    candles: CandlesSlice<'a>,
+    position: usize,
+}
+
+impl<'a> CandlesCursor<'a> {
+    pub fn new(candles: CandlesSlice<'a>) -> Self {
+        CandlesCursor {
+            candles,
+            position: 0,
+        }
+    }
+
+    /// Number of bars consumed so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.candles.len() - self.position
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.position == self.candles.len()
+    }
+
+    /// The last consumed bar.
+    pub fn current(&self) -> Option<CandleRef<'a>> {
+        self.candles.at(self.position.checked_sub(1)?)
+    }
+
+    /// The bar the next call to `next` returns.
+    pub fn peek(&self) -> Option<CandleRef<'a>> {
+        self.candles.at(self.position)
+    }
+
+    /// Moves back or forward so that `next` returns the first bar opening at
+    /// or after `time`, and returns that bar.
+    pub fn seek_time(&mut self, time: DateTime<Utc>) -> Option<CandleRef<'a>> {
+        self.position = self.candles.time.partition_point(|t| *t < time);
+        self.peek()
+    }
+
+    pub fn reset(&mut self) {
+        self.position = 0;
+    }
+
+    /// Up to `n` consumed bars ending with the current one.
+    pub fn history(&self, n: usize) -> CandlesSlice<'a> {
+        let start = self.position.saturating_sub(n);
+        self.candles.slice(start..self.position).unwrap()
+    }
+}
+
+impl<'a> Iterator for CandlesCursor<'a> {
+    type Item = CandleRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let candle = self.peek()?;
+        self.position += 1;
+        Some(candle)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining(), Some(self.remaining()))
+    }
+}
+
+impl ExactSizeIterator for CandlesCursor<'_> {}
+
+impl Candles {
+    pub fn cursor(&self) -> CandlesCursor<'_> {
+        CandlesCursor::new(self.as_slice())
+    }
+}
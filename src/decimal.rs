@@ -0,0 +1,172 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    candle::{check_push, Candles, PushError},
+    symbol::Symbol,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DecimalCandle {
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub volume: Option<Decimal>,
+    pub time: DateTime<Utc>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Symbol::is_empty")
+    )]
+    pub id: Symbol,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DecimalCandles {
+    pub id: Symbol,
+    pub open: Vec<Decimal>,
+    pub high: Vec<Decimal>,
+    pub low: Vec<Decimal>,
+    pub close: Vec<Decimal>,
+    pub volume: Option<Vec<Decimal>>,
+    pub time: Vec<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecimalConversionError {
+    pub index: usize,
+    pub column: &'static str,
+}
+
+impl fmt::Display for DecimalConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at index {} is not representable as a decimal",
+            self.column, self.index
+        )
+    }
+}
+
+impl std::error::Error for DecimalConversionError {}
+
+fn to_decimals(xs: &[f64], column: &'static str) -> Result<Vec<Decimal>, DecimalConversionError> {
+    xs.iter()
+        .enumerate()
+        .map(|(index, &x)| {
+            Decimal::try_from(x).map_err(|_| DecimalConversionError { index, column })
+        })
+        .collect()
+}
+
+fn to_f64s(xs: &[Decimal]) -> Vec<f64> {
+    xs.iter()
+        .map(|&x| f64::try_from(x).unwrap_or(f64::NAN))
+        .collect()
+}
+
+impl DecimalCandles {
+    pub fn len(&self) -> usize {
+        self.time.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.time.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<DecimalCandle> {
+        (index < self.time.len()).then(|| DecimalCandle {
+            id: self.id.clone(),
+            open: self.open[index],
+            high: self.high[index],
+            low: self.low[index],
+            close: self.close[index],
+            volume: self.volume.as_ref().map(|xs| xs[index]),
+            time: self.time[index],
+        })
+    }
+
+    pub fn last(&self) -> Option<DecimalCandle> {
+        self.len().checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = DecimalCandle> + '_ {
+        (0..self.len()).filter_map(|index| self.get(index))
+    }
+
+    pub fn try_push(&mut self, candle: DecimalCandle) -> Result<(), PushError> {
+        let adopt = check_push(
+            &self.id,
+            self.volume.is_some(),
+            self.time.last().copied(),
+            &candle.id,
+            candle.time,
+            candle.volume.is_some(),
+        )?;
+        if adopt.id {
+            self.id = candle.id.clone();
+        }
+        if adopt.volume {
+            self.volume = Some(Vec::new());
+        }
+        if let (Some(xs), Some(volume)) = (self.volume.as_mut(), candle.volume) {
+            xs.push(volume);
+        }
+        self.open.push(candle.open);
+        self.high.push(candle.high);
+        self.low.push(candle.low);
+        self.close.push(candle.close);
+        self.time.push(candle.time);
+        Ok(())
+    }
+
+    /// Lossy conversion back to `f64` columns; extra columns are not carried.
+    pub fn to_candles(&self) -> Candles {
+        Candles {
+            id: self.id.clone(),
+            open: to_f64s(&self.open),
+            high: to_f64s(&self.high),
+            low: to_f64s(&self.low),
+            close: to_f64s(&self.close),
+            volume: self.volume.as_deref().map(to_f64s),
+            time: self.time.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+impl TryFrom<&Candles> for DecimalCandles {
+    type Error = DecimalConversionError;
+
+    fn try_from(candles: &Candles) -> Result<Self, Self::Error> {
+        Ok(DecimalCandles {
+            id: candles.id.clone(),
+            open: to_decimals(&candles.open, "open")?,
+            high: to_decimals(&candles.high, "high")?,
+            low: to_decimals(&candles.low, "low")?,
+            close: to_decimals(&candles.close, "close")?,
+            volume: candles
+                .volume
+                .as_deref()
+                .map(|xs| to_decimals(xs, "volume"))
+                .transpose()?,
+            time: candles.time.clone(),
+        })
+    }
+}
+
+impl From<&DecimalCandles> for Candles {
+    fn from(candles: &DecimalCandles) -> Self {
+        candles.to_candles()
+    }
+}
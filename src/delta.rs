@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{candle::Candles, merge::MergeError};
+
+/// The bars opening at or after `since`, which replace the receiver's bars
+/// from `since` on. Send the receiver's last bar time as `since` so an
+/// updated forming bar is resent along with the new ones.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CandlesDelta {
+    pub since: DateTime<Utc>,
+    pub candles: Candles,
+}
+
+impl CandlesDelta {
+    pub fn len(&self) -> usize {
+        self.candles.time.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.candles.time.is_empty()
+    }
+}
+
+impl Candles {
+    pub fn delta_since(&self, since: DateTime<Utc>) -> CandlesDelta {
+        let start = self.time.partition_point(|&t| t < since);
+        CandlesDelta {
+            since,
+            candles: self.copy_range(start..self.time.len()),
+        }
+    }
+
+    /// Replaces the bars opening at or after `delta.since` with the delta's.
+    /// Leaves the series untouched on error.
+    pub fn apply_delta(&mut self, delta: &CandlesDelta) -> Result<(), MergeError> {
+        self.check_compatible(&delta.candles)?;
+        if let Some(first) = delta.candles.time.first() {
+            if *first < delta.since {
+                return Err(MergeError::Overlap {
+                    last: delta.since,
+                    first: *first,
+                });
+            }
+        }
+        let keep = self.time.partition_point(|&t| t < delta.since);
+        self.truncate(keep);
+        self.append(&delta.candles)
+    }
+}
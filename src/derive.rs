@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+
+use crate::{candle::Candles, kernels, parallel::map_indices};
+
+impl Candles {
+    /// Simple close-to-close returns, NaN at index 0.
+    pub fn returns(&self) -> Vec<f64> {
+        std::iter::once(f64::NAN)
+            .chain(self.close.windows(2).map(|pair| pair[1] / pair[0] - 1.0))
+            .take(self.close.len())
+            .collect()
+    }
+
+    /// Close-to-close log returns, NaN at index 0.
+    pub fn log_returns(&self) -> Vec<f64> {
+        std::iter::once(f64::NAN)
+            .chain(self.close.windows(2).map(|pair| (pair[1] / pair[0]).ln()))
+            .take(self.close.len())
+            .collect()
+    }
+
+    pub fn typical_price(&self) -> Vec<f64> {
+        (0..self.time.len())
+            .map(|i| (self.high[i] + self.low[i] + self.close[i]) / 3.0)
+            .collect()
+    }
+
+    pub fn median_price(&self) -> Vec<f64> {
+        self.hl2()
+    }
+
+    pub fn hl2(&self) -> Vec<f64> {
+        (0..self.time.len())
+            .map(|i| (self.high[i] + self.low[i]) / 2.0)
+            .collect()
+    }
+
+    pub fn ohlc4(&self) -> Vec<f64> {
+        (0..self.time.len())
+            .map(|i| (self.open[i] + self.high[i] + self.low[i] + self.close[i]) / 4.0)
+            .collect()
+    }
+
+    /// Cumulative VWAP of the typical price from the first bar, `None` without volume.
+    pub fn vwap(&self) -> Option<Vec<f64>> {
+        self.vwap_from(0)
+    }
+
+    /// Cumulative VWAP of the typical price from the first bar opening at or
+    /// after `anchor`, NaN before it; `None` without volume.
+    pub fn anchored_vwap(&self, anchor: DateTime<Utc>) -> Option<Vec<f64>> {
+        self.vwap_from(self.time.partition_point(|time| *time < anchor))
+    }
+
+    fn vwap_from(&self, start: usize) -> Option<Vec<f64>> {
+        let volume = self.volume.as_ref()?;
+        let (mut pv, mut v) = (0.0, 0.0);
+        Some(
+            (0..self.time.len())
+                .map(|i| {
+                    if i < start {
+                        return f64::NAN;
+                    }
+                    let price = (self.high[i] + self.low[i] + self.close[i]) / 3.0;
+                    pv += price * volume[i];
+                    v += volume[i];
+                    pv / v
+                })
+                .collect(),
+        )
+    }
+
+    /// VWAP of the typical price over each trailing window of `period` bars,
+    /// NaN for the first `period - 1` bars and for windows without volume;
+    /// `None` without a volume column.
+    pub fn rolling_vwap(&self, period: usize) -> Option<Vec<f64>> {
+        let volume = self.volume.as_ref()?;
+        let len = self.time.len();
+        if period == 0 || len < period {
+            return Some(vec![f64::NAN; len]);
+        }
+        let price = self.typical_price();
+        let mut out = vec![f64::NAN; period - 1];
+        out.extend(map_indices(len - period + 1, |i| {
+            let window = i..i + period;
+            kernels::dot(&price[window.clone()], &volume[window.clone()])
+                / kernels::sum(&volume[window])
+        }));
+        Some(out)
+    }
+
+    /// Highest high over each trailing window of `period` bars.
+    pub fn highest_high(&self, period: usize) -> Vec<f64> {
+        kernels::rolling_max(&self.high, period)
+    }
+
+    /// Lowest low over each trailing window of `period` bars.
+    pub fn lowest_low(&self, period: usize) -> Vec<f64> {
+        kernels::rolling_min(&self.low, period)
+    }
+}
@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+
+use crate::candle::Candles;
+
+/// One difference found by [`Candles::diff`]. Indices address the series the
+/// bar comes from: `index` into `self`, `other_index` into `other`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CandleDiff {
+    /// Bar present in `self` only.
+    MissingInOther { index: usize, time: DateTime<Utc> },
+    /// Bar present in `other` only.
+    MissingInSelf {
+        other_index: usize,
+        time: DateTime<Utc>,
+    },
+    Mismatch {
+        index: usize,
+        other_index: usize,
+        time: DateTime<Utc>,
+        column: &'static str,
+        value: f64,
+        other: f64,
+    },
+}
+
+impl CandleDiff {
+    pub fn time(&self) -> DateTime<Utc> {
+        match *self {
+            CandleDiff::MissingInOther { time, .. }
+            | CandleDiff::MissingInSelf { time, .. }
+            | CandleDiff::Mismatch { time, .. } => time,
+        }
+    }
+}
+
+/// Equal within `tolerance` relative to the larger magnitude; two NaNs match.
+fn close_enough(a: f64, b: f64, tolerance: f64) -> bool {
+    (a.is_nan() && b.is_nan()) || (a - b).abs() <= tolerance * a.abs().max(b.abs())
+}
+
+impl Candles {
+    /// Walks both sorted series by time and reports bars missing from either
+    /// side and columns differing by more than `tolerance`, a relative error
+    /// such as `1e-6`. Volume is compared only when both series carry it.
+    pub fn diff(&self, other: &Candles, tolerance: f64) -> Vec<CandleDiff> {
+        let mut diffs = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.time.len() || j < other.time.len() {
+            match (self.time.get(i), other.time.get(j)) {
+                (Some(&a), Some(&b)) if a == b => {
+                    let mut columns = vec![
+                        ("open", self.open[i], other.open[j]),
+                        ("high", self.high[i], other.high[j]),
+                        ("low", self.low[i], other.low[j]),
+                        ("close", self.close[i], other.close[j]),
+                    ];
+                    if let (Some(xs), Some(ys)) = (&self.volume, &other.volume) {
+                        columns.push(("volume", xs[i], ys[j]));
+                    }
+                    diffs.extend(
+                        columns
+                            .into_iter()
+                            .filter(|&(_, x, y)| !close_enough(x, y, tolerance))
+                            .map(|(column, value, other)| CandleDiff::Mismatch {
+                                index: i,
+                                other_index: j,
+                                time: a,
+                                column,
+                                value,
+                                other,
+                            }),
+                    );
+                    i += 1;
+                    j += 1;
+                }
+                (Some(&a), Some(&b)) if b < a => {
+                    diffs.push(CandleDiff::MissingInSelf {
+                        other_index: j,
+                        time: b,
+                    });
+                    j += 1;
+                }
+                (Some(&a), _) => {
+                    diffs.push(CandleDiff::MissingInOther { index: i, time: a });
+                    i += 1;
+                }
+                (None, Some(&b)) => {
+                    diffs.push(CandleDiff::MissingInSelf {
+                        other_index: j,
+                        time: b,
+                    });
+                    j += 1;
+                }
+                (None, None) => break,
+            }
+        }
+        diffs
+    }
+
+    /// Same id, times, labels and columns, with every value within
+    /// `epsilon` of its counterpart; two NaNs match.
+    pub fn approx_eq(&self, other: &Candles, epsilon: f64) -> bool {
+        let column = |xs: &[f64], ys: &[f64]| {
+            xs.len() == ys.len()
+                && xs
+                    .iter()
+                    .zip(ys)
+                    .all(|(x, y)| (x.is_nan() && y.is_nan()) || (x - y).abs() <= epsilon)
+        };
+        self.id == other.id
+            && self.time == other.time
+            && self.labels == other.labels
+            && column(&self.open, &other.open)
+            && column(&self.high, &other.high)
+            && column(&self.low, &other.low)
+            && column(&self.close, &other.close)
+            && match (&self.volume, &other.volume) {
+                (Some(xs), Some(ys)) => column(xs, ys),
+                (None, None) => true,
+                _ => false,
+            }
+            && self.extra.len() == other.extra.len()
+            && self
+                .extra
+                .iter()
+                .all(|(name, xs)| other.extra.get(name).is_some_and(|ys| column(xs, ys)))
+    }
+}
@@ -0,0 +1,84 @@
+//! Drawdowns of the close, as of an equity curve. NaN closes are skipped.
+
+use chrono::{DateTime, Duration, Utc};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::candle::Candles;
+
+/// A decline from a peak close to the trough below it, until a close regains
+/// the peak.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Drawdown {
+    pub peak: DateTime<Utc>,
+    pub trough: DateTime<Utc>,
+    /// First bar closing at or above the peak, `None` while still underwater.
+    pub recovery: Option<DateTime<Utc>>,
+    /// Decline from the peak to the trough as a fraction of the peak.
+    pub depth: f64,
+}
+
+impl Drawdown {
+    pub fn is_recovered(&self) -> bool {
+        self.recovery.is_some()
+    }
+
+    /// Time from the peak to the recovery.
+    pub fn duration(&self) -> Option<Duration> {
+        self.recovery.map(|recovery| recovery - self.peak)
+    }
+}
+
+impl Candles {
+    /// Every drawdown in time order; the last one may not have recovered.
+    pub fn drawdowns(&self) -> Vec<Drawdown> {
+        let mut drawdowns = Vec::new();
+        let mut peak: Option<usize> = None;
+        let mut current: Option<Drawdown> = None;
+        for (i, &close) in self.close.iter().enumerate() {
+            if close.is_nan() {
+                continue;
+            }
+            let Some(p) = peak.filter(|&p| close < self.close[p]) else {
+                if let Some(mut drawdown) = current.take() {
+                    drawdown.recovery = Some(self.time[i]);
+                    drawdowns.push(drawdown);
+                }
+                peak = Some(i);
+                continue;
+            };
+            let depth = (self.close[p] - close) / self.close[p];
+            match current.as_mut() {
+                Some(drawdown) if depth <= drawdown.depth => {}
+                Some(drawdown) => {
+                    drawdown.trough = self.time[i];
+                    drawdown.depth = depth;
+                }
+                None => {
+                    current = Some(Drawdown {
+                        peak: self.time[p],
+                        trough: self.time[i],
+                        recovery: None,
+                        depth,
+                    })
+                }
+            }
+        }
+        drawdowns.extend(current);
+        drawdowns
+    }
+
+    /// Distance of each close below the highest close so far, as a fraction
+    /// of that peak: zero at new highs and negative below them.
+    pub fn underwater_curve(&self) -> Vec<f64> {
+        let mut peak = f64::NEG_INFINITY;
+        self.close
+            .iter()
+            .map(|&close| {
+                peak = peak.max(close);
+                (close - peak) / peak
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,173 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    candle::{Candle, Candles, VolumePolicy},
+    symbol::Symbol,
+    validation::ValidationError,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SetError {
+    OutOfBounds {
+        index: usize,
+        len: usize,
+    },
+    IdMismatch {
+        expected: Symbol,
+        found: Symbol,
+    },
+    VolumeMismatch {
+        expected: bool,
+    },
+    /// The bar breaks an OHLC invariant or the time order with its
+    /// neighbours.
+    Invalid(ValidationError),
+}
+
+impl fmt::Display for SetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetError::OutOfBounds { index, len } => {
+                write!(f, "index {index} out of bounds for {len} bars")
+            }
+            SetError::IdMismatch { expected, found } => {
+                write!(f, "candle id {found} does not match series id {expected}")
+            }
+            SetError::VolumeMismatch { expected: true } => {
+                write!(f, "candle has no volume but series has a volume column")
+            }
+            SetError::VolumeMismatch { expected: false } => {
+                write!(f, "candle has volume but series has no volume column")
+            }
+            SetError::Invalid(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for SetError {}
+
+/// Staged edit of one bar. Nothing reaches the series until
+/// [`CandleMut::commit`] validates the bar and writes every column at once;
+/// dropping it discards the edit.
+#[derive(Debug)]
+pub struct CandleMut<'a> {
+    candles: &'a mut Candles,
+    index: usize,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: Option<f64>,
+    pub time: DateTime<Utc>,
+}
+
+impl CandleMut<'_> {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn commit(self) -> Result<(), SetError> {
+        let candle = Candle {
+            open: self.open,
+            high: self.high,
+            low: self.low,
+            close: self.close,
+            volume: self.volume,
+            time: self.time,
+            id: self.candles.id.clone(),
+        };
+        self.candles.write_row(self.index, &candle)
+    }
+}
+
+impl Candles {
+    /// Overwrites bar `index` with `candle`, checking that it is a valid bar
+    /// that keeps the time order. An empty candle id matches any series.
+    pub fn set(&mut self, index: usize, candle: &Candle) -> Result<(), SetError> {
+        if !candle.id.is_empty() && candle.id != self.id {
+            return Err(SetError::IdMismatch {
+                expected: self.id.clone(),
+                found: candle.id.clone(),
+            });
+        }
+        self.write_row(index, candle)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<CandleMut<'_>> {
+        if index >= self.time.len() {
+            return None;
+        }
+        Some(CandleMut {
+            open: self.open[index],
+            high: self.high[index],
+            low: self.low[index],
+            close: self.close[index],
+            volume: self.volume.as_ref().map(|xs| xs[index]),
+            time: self.time[index],
+            candles: self,
+            index,
+        })
+    }
+
+    pub fn last_mut(&mut self) -> Option<CandleMut<'_>> {
+        let len = self.time.len();
+        self.get_mut(len.checked_sub(1)?)
+    }
+
+    fn write_row(&mut self, index: usize, candle: &Candle) -> Result<(), SetError> {
+        let Candle {
+            open,
+            high,
+            low,
+            close,
+            volume,
+            time,
+            ..
+        } = *candle;
+        let len = self.time.len();
+        if index >= len {
+            return Err(SetError::OutOfBounds { index, len });
+        }
+        let volume = volume.filter(|_| self.volume_policy != VolumePolicy::Absent);
+        if volume.is_some() != self.volume.is_some() {
+            return Err(SetError::VolumeMismatch {
+                expected: self.volume.is_some(),
+            });
+        }
+        let invalid = |err| Err(SetError::Invalid(err));
+        let columns = [
+            ("open", open),
+            ("high", high),
+            ("low", low),
+            ("close", close),
+        ];
+        for (column, x) in columns.into_iter().chain(volume.map(|v| ("volume", v))) {
+            if !x.is_finite() {
+                return invalid(ValidationError::NonFinite { index, column });
+            }
+        }
+        if high < open.max(close) {
+            return invalid(ValidationError::HighBelowBody { index });
+        }
+        if low > open.min(close) {
+            return invalid(ValidationError::LowAboveBody { index });
+        }
+        if index > 0 && time <= self.time[index - 1] {
+            return invalid(ValidationError::NonIncreasingTime { index });
+        }
+        if index + 1 < len && time >= self.time[index + 1] {
+            return invalid(ValidationError::NonIncreasingTime { index: index + 1 });
+        }
+        self.open[index] = open;
+        self.high[index] = high;
+        self.low[index] = low;
+        self.close[index] = close;
+        if let (Some(xs), Some(v)) = (self.volume.as_mut(), volume) {
+            xs[index] = v;
+        }
+        self.time[index] = time;
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+//! Crate-level error. Each operation keeps its own error type; this wraps
+//! them all so callers mixing operations can use `?` with one type.
+
+use std::io;
+
+use thiserror::Error;
+
+use crate::{
+    candle::PushError, causal::CausalError, edit::SetError, merge::MergeError, replay::ReplayError,
+    timestamp::TimestampError, validation::ValidationError,
+};
+
+#[derive(Debug, Error)]
+pub enum ErfurtError {
+    #[error("index {index} out of bounds for {len} bars")]
+    OutOfBounds { index: usize, len: usize },
+    /// A column is shorter than the time column.
+    #[error("column {column} has no value at index {index}")]
+    MissingValue { column: &'static str, index: usize },
+    /// The time at `index` is not after the one before it.
+    #[error("time at index {index} is not increasing")]
+    Unsorted { index: usize },
+    #[error("series has no volume column")]
+    NoVolume,
+    #[error("invalid series: {}", .0.first().map_or_else(String::new, ToString::to_string))]
+    Invalid(Vec<ValidationError>),
+    #[error(transparent)]
+    Push(#[from] PushError),
+    #[error(transparent)]
+    Merge(#[from] MergeError),
+    #[error(transparent)]
+    Set(#[from] SetError),
+    #[error(transparent)]
+    Causal(#[from] CausalError),
+    #[error(transparent)]
+    Replay(#[from] ReplayError),
+    #[error(transparent)]
+    Timestamp(#[from] TimestampError),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[cfg(any(feature = "ndarray", feature = "nalgebra"))]
+    #[error(transparent)]
+    Matrix(#[from] crate::matrix::MatrixError),
+    #[cfg(feature = "ndjson")]
+    #[error(transparent)]
+    Ndjson(#[from] crate::ndjson::NdjsonError),
+    #[cfg(feature = "arrow")]
+    #[error(transparent)]
+    Parquet(#[from] parquet::errors::ParquetError),
+    #[cfg(feature = "plot")]
+    #[error(transparent)]
+    Plot(#[from] crate::plot::PlotError),
+    #[cfg(feature = "storage")]
+    #[error(transparent)]
+    Storage(#[from] crate::storage::StorageError),
+    #[cfg(any(feature = "msgpack", feature = "bincode"))]
+    #[error(transparent)]
+    Blob(#[from] crate::blob::BlobError),
+}
+
+impl From<Vec<ValidationError>> for ErfurtError {
+    fn from(errors: Vec<ValidationError>) -> Self {
+        ErfurtError::Invalid(errors)
+    }
+}
+
+pub type Result<T, E = ErfurtError> = std::result::Result<T, E>;
@@ -0,0 +1,264 @@
+use std::{fmt, marker::PhantomData};
+
+use chrono::{DateTime, Utc};
+use serde::{
+    de::{self, IgnoredAny, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+
+use crate::{
+    candle::{Candles, CandlesExt},
+    extra,
+    symbol::Symbol,
+};
+
+struct Number(f64);
+
+impl<'de> Deserialize<'de> for Number {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NumberVisitor;
+
+        impl Visitor<'_> for NumberVisitor {
+            type Value = Number;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a number or a string-encoded number")
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Number, E> {
+                Ok(Number(value))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Number, E> {
+                Ok(Number(value as f64))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Number, E> {
+                Ok(Number(value as f64))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Number, E> {
+                value.parse().map(Number).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(NumberVisitor)
+    }
+}
+
+fn next<'de, A: SeqAccess<'de>>(seq: &mut A, index: usize) -> Result<f64, A::Error> {
+    seq.next_element::<Number>()?
+        .map(|number| number.0)
+        .ok_or_else(|| de::Error::invalid_length(index, &"more kline fields"))
+}
+
+fn next_time<'de, A: SeqAccess<'de>>(
+    seq: &mut A,
+    index: usize,
+    scale: i64,
+) -> Result<DateTime<Utc>, A::Error> {
+    let value = next(seq, index)? as i64;
+    value
+        .checked_mul(scale)
+        .and_then(DateTime::from_timestamp_millis)
+        .ok_or_else(|| de::Error::custom(format!("timestamp {value} out of range")))
+}
+
+fn drain<'de, A: SeqAccess<'de>>(seq: &mut A) -> Result<(), A::Error> {
+    while seq.next_element::<IgnoredAny>()?.is_some() {}
+    Ok(())
+}
+
+trait Kline: Sized {
+    const EXPECTING: &'static str;
+
+    fn from_seq<'de, A: SeqAccess<'de>>(seq: &mut A) -> Result<Self, A::Error>;
+}
+
+struct KlineVisitor<K>(PhantomData<K>);
+
+impl<'de, K: Kline> Visitor<'de> for KlineVisitor<K> {
+    type Value = K;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(K::EXPECTING)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<K, A::Error> {
+        let kline = K::from_seq(&mut seq)?;
+        drain(&mut seq)?;
+        Ok(kline)
+    }
+}
+
+/// `[open time ms, open, high, low, close, volume, close time ms, quote volume,
+/// trades, taker buy base volume, taker buy quote volume, ...]`
+#[derive(Clone, Debug, PartialEq)]
+pub struct BinanceKline {
+    pub open_time: DateTime<Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    pub close_time: DateTime<Utc>,
+    pub quote_volume: f64,
+    pub trades: u64,
+    pub taker_buy_base_volume: f64,
+    pub taker_buy_quote_volume: f64,
+}
+
+impl Kline for BinanceKline {
+    const EXPECTING: &'static str = "a binance kline array";
+
+    fn from_seq<'de, A: SeqAccess<'de>>(seq: &mut A) -> Result<Self, A::Error> {
+        Ok(BinanceKline {
+            open_time: next_time(seq, 0, 1)?,
+            open: next(seq, 1)?,
+            high: next(seq, 2)?,
+            low: next(seq, 3)?,
+            close: next(seq, 4)?,
+            volume: next(seq, 5)?,
+            close_time: next_time(seq, 6, 1)?,
+            quote_volume: next(seq, 7)?,
+            trades: next(seq, 8)? as u64,
+            taker_buy_base_volume: next(seq, 9)?,
+            taker_buy_quote_volume: next(seq, 10)?,
+        })
+    }
+}
+
+/// `[start time ms, open, high, low, close, volume, turnover]`, as found in
+/// `result.list` of the v5 kline endpoint.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BybitKline {
+    pub start_time: DateTime<Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    pub turnover: f64,
+}
+
+impl Kline for BybitKline {
+    const EXPECTING: &'static str = "a bybit kline array";
+
+    fn from_seq<'de, A: SeqAccess<'de>>(seq: &mut A) -> Result<Self, A::Error> {
+        Ok(BybitKline {
+            start_time: next_time(seq, 0, 1)?,
+            open: next(seq, 1)?,
+            high: next(seq, 2)?,
+            low: next(seq, 3)?,
+            close: next(seq, 4)?,
+            volume: next(seq, 5)?,
+            turnover: next(seq, 6)?,
+        })
+    }
+}
+
+/// `[time s, low, high, open, close, volume]`
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoinbaseCandle {
+    pub time: DateTime<Utc>,
+    pub low: f64,
+    pub high: f64,
+    pub open: f64,
+    pub close: f64,
+    pub volume: f64,
+}
+
+impl Kline for CoinbaseCandle {
+    const EXPECTING: &'static str = "a coinbase candle array";
+
+    fn from_seq<'de, A: SeqAccess<'de>>(seq: &mut A) -> Result<Self, A::Error> {
+        Ok(CoinbaseCandle {
+            time: next_time(seq, 0, 1000)?,
+            low: next(seq, 1)?,
+            high: next(seq, 2)?,
+            open: next(seq, 3)?,
+            close: next(seq, 4)?,
+            volume: next(seq, 5)?,
+        })
+    }
+}
+
+macro_rules! impl_deserialize {
+    ($($kline:ty),*) => {
+        $(
+            impl<'de> Deserialize<'de> for $kline {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserializer.deserialize_seq(KlineVisitor(PhantomData))
+                }
+            }
+        )*
+    };
+}
+
+impl_deserialize!(BinanceKline, BybitKline, CoinbaseCandle);
+
+type ExtraField<K> = (&'static str, fn(&K) -> f64);
+
+fn collect<K>(
+    id: Symbol,
+    klines: &[K],
+    row: impl Fn(&K) -> (f64, f64, f64, f64, f64, DateTime<Utc>),
+    extra: &[ExtraField<K>],
+) -> Candles {
+    let mut candles = Candles::with_capacity(id, klines.len(), true);
+    for kline in klines {
+        let (open, high, low, close, volume, time) = row(kline);
+        candles.push_row(open, high, low, close, Some(volume), time);
+    }
+    for (name, value) in extra {
+        candles
+            .extra
+            .insert(*name, klines.iter().map(value).collect());
+    }
+    candles.sort_by_time();
+    candles
+}
+
+impl Candles {
+    /// Keeps the quote volume, trade count and the order flow implied by the
+    /// taker buy volume as extra columns.
+    pub fn from_binance_klines(id: impl Into<Symbol>, klines: &[BinanceKline]) -> Candles {
+        let mut candles = collect(
+            id.into(),
+            klines,
+            |k| (k.open, k.high, k.low, k.close, k.volume, k.open_time),
+            &[
+                (extra::QUOTE_VOLUME, |k| k.quote_volume),
+                (extra::TRADES, |k| k.trades as f64),
+                (extra::BUY_VOLUME, |k| k.taker_buy_base_volume),
+                (extra::SELL_VOLUME, |k| k.volume - k.taker_buy_base_volume),
+            ],
+        );
+        if let Some((buy, sell)) = candles.buy_volume().zip(candles.sell_volume()) {
+            let delta = crate::bars::cumulative_delta(buy, sell);
+            candles.extra.insert(extra::CUMULATIVE_DELTA, delta);
+        }
+        candles
+    }
+
+    /// Bybit returns klines newest first; the result is sorted by time. The
+    /// turnover is kept as the quote volume extra column.
+    pub fn from_bybit_klines(id: impl Into<Symbol>, klines: &[BybitKline]) -> Candles {
+        collect(
+            id.into(),
+            klines,
+            |k| (k.open, k.high, k.low, k.close, k.volume, k.start_time),
+            &[(extra::QUOTE_VOLUME, |k| k.turnover)],
+        )
+    }
+
+    /// Coinbase returns candles newest first; the result is sorted by time.
+    pub fn from_coinbase_candles(id: impl Into<Symbol>, candles: &[CoinbaseCandle]) -> Candles {
+        collect(
+            id.into(),
+            candles,
+            |k| (k.open, k.high, k.low, k.close, k.volume, k.time),
+            &[],
+        )
+    }
+}
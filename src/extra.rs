@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub const TRADES: &str = "trades";
+pub const QUOTE_VOLUME: &str = "quote_volume";
+pub const OPEN_INTEREST: &str = "open_interest";
+pub const BID_CLOSE: &str = "bid_close";
+pub const ASK_CLOSE: &str = "ask_close";
+/// Volume of trades whose aggressor bought.
+pub const BUY_VOLUME: &str = "buy_volume";
+/// Volume of trades whose aggressor sold.
+pub const SELL_VOLUME: &str = "sell_volume";
+/// Running sum of buy minus sell volume up to and including each bar.
+pub const CUMULATIVE_DELTA: &str = "cumulative_delta";
+
+/// Named columns carried alongside OHLCV, each aligned to the time column.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct ExtraColumns(BTreeMap<String, Vec<f64>>);
+
+impl ExtraColumns {
+    pub const fn new() -> Self {
+        ExtraColumns(BTreeMap::new())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&[f64]> {
+        self.0.get(name).map(Vec::as_slice)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Vec<f64>> {
+        self.0.get_mut(name)
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, values: Vec<f64>) -> Option<Vec<f64>> {
+        self.0.insert(name.into(), values)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Vec<f64>> {
+        self.0.remove(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[f64])> {
+        self.0
+            .iter()
+            .map(|(name, values)| (name.as_str(), values.as_slice()))
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (&str, &mut Vec<f64>)> {
+        self.0
+            .iter_mut()
+            .map(|(name, values)| (name.as_str(), values))
+    }
+
+    pub(crate) fn map(&self, f: impl Fn(&[f64]) -> Vec<f64>) -> ExtraColumns {
+        ExtraColumns(
+            self.0
+                .iter()
+                .map(|(name, values)| (name.clone(), f(values)))
+                .collect(),
+        )
+    }
+
+    pub(crate) fn empty_like(&self) -> ExtraColumns {
+        self.map(|_| Vec::new())
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, Vec<f64>)> for ExtraColumns {
+    fn from_iter<I: IntoIterator<Item = (S, Vec<f64>)>>(iter: I) -> Self {
+        ExtraColumns(
+            iter.into_iter()
+                .map(|(name, values)| (name.into(), values))
+                .collect(),
+        )
+    }
+}
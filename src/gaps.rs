@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+
+use crate::{candle::Candles, timeframe::Timeframe};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GapFill {
+    /// Flat candle at the previous close with zero volume.
+    ForwardFillClose,
+    /// Candle with all prices and volume set to NaN.
+    Nan,
+    Skip,
+}
+
+impl Candles {
+    /// True when every bar opens on a `timeframe` boundary counted from the
+    /// Unix epoch.
+    pub fn is_aligned(&self, timeframe: Timeframe) -> bool {
+        self.time.iter().all(|&time| timeframe.is_aligned(time))
+    }
+
+    /// Missing ranges as `(first missing bar time, next present bar time)`.
+    /// A non-positive timeframe has no bars to miss and finds none.
+    pub fn find_gaps(&self, timeframe: Timeframe) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+        if timeframe.as_seconds() <= 0 {
+            return Vec::new();
+        }
+        let step = timeframe.duration();
+        self.time
+            .windows(2)
+            .filter(|pair| pair[1] - pair[0] > step)
+            .map(|pair| (pair[0] + step, pair[1]))
+            .collect()
+    }
+
+    /// A copy with the gaps [`Candles::find_gaps`] reports filled in; a
+    /// non-positive timeframe returns a plain copy.
+    pub fn fill_gaps(&self, timeframe: Timeframe, strategy: GapFill) -> Candles {
+        let fill: fn(f64) -> (f64, f64) = match strategy {
+            GapFill::ForwardFillClose => |close| (close, 0.0),
+            GapFill::Nan => |_| (f64::NAN, f64::NAN),
+            GapFill::Skip => return self.clone(),
+        };
+        if timeframe.as_seconds() <= 0 {
+            return self.clone();
+        }
+        let step = timeframe.duration();
+        let mut filled = self.empty_like();
+        for i in 0..self.time.len() {
+            if i > 0 {
+                let mut time = self.time[i - 1] + step;
+                while time < self.time[i] {
+                    let (price, volume) = fill(self.close[i - 1]);
+                    filled.push_row(price, price, price, price, Some(volume), time);
+                    time += step;
+                }
+            }
+            filled.push_index(self, i);
+        }
+        filled
+    }
+}
@@ -0,0 +1,84 @@
+//! Stable content digest: 128-bit FNV-1a over a fixed little-endian
+//! encoding, so the value only changes when the data does, across platforms
+//! and releases. It detects accidental changes, not tampering.
+//!
+//! Encoded in order: a version tag, the id, the bar count, each time as i64
+//! seconds and u32 nanoseconds, the open, high, low and close columns, the
+//! volume column behind a presence byte, each extra column by name, then
+//! the labels as u32 when present, so unlabeled series keep their digest.
+//! Floats are canonicalized first: `-0.0` hashes as `0.0` and every NaN as
+//! the same quiet NaN.
+
+use crate::candle::Candles;
+
+const TAG: &[u8] = b"erfurt-candles-v1";
+const OFFSET: u128 = 0x6c62272e07bb014262b821756295c58d;
+const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+struct Fnv(u128);
+
+impl Fnv {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u128::from(byte);
+            self.0 = self.0.wrapping_mul(PRIME);
+        }
+    }
+
+    fn write_len(&mut self, len: usize) {
+        self.write(&(len as u64).to_le_bytes());
+    }
+
+    fn write_str(&mut self, s: &str) {
+        self.write_len(s.len());
+        self.write(s.as_bytes());
+    }
+
+    fn write_f64s(&mut self, xs: &[f64]) {
+        for &x in xs {
+            let x = if x.is_nan() {
+                f64::NAN
+            } else if x == 0.0 {
+                0.0
+            } else {
+                x
+            };
+            self.write(&x.to_bits().to_le_bytes());
+        }
+    }
+}
+
+impl Candles {
+    pub fn content_hash(&self) -> u128 {
+        let mut hash = Fnv(OFFSET);
+        hash.write(TAG);
+        hash.write_str(&self.id);
+        hash.write_len(self.time.len());
+        for time in &self.time {
+            hash.write(&time.timestamp().to_le_bytes());
+            hash.write(&time.timestamp_subsec_nanos().to_le_bytes());
+        }
+        for column in [&self.open, &self.high, &self.low, &self.close] {
+            hash.write_f64s(column);
+        }
+        match &self.volume {
+            Some(volume) => {
+                hash.write(&[1]);
+                hash.write_f64s(volume);
+            }
+            None => hash.write(&[0]),
+        }
+        hash.write_len(self.extra.len());
+        for (name, column) in self.extra.iter() {
+            hash.write_str(name);
+            hash.write_f64s(column);
+        }
+        if let Some(labels) = &self.labels {
+            hash.write(b"labels");
+            for label in labels {
+                hash.write(&label.to_le_bytes());
+            }
+        }
+        hash.0
+    }
+}
@@ -0,0 +1,36 @@
+use crate::candle::Candles;
+
+impl Candles {
+    pub fn to_heikin_ashi(&self) -> Candles {
+        let len = self.time.len();
+        let mut open = Vec::with_capacity(len);
+        let mut high = Vec::with_capacity(len);
+        let mut low = Vec::with_capacity(len);
+        let mut close = Vec::with_capacity(len);
+        for i in 0..len {
+            let ha_close = (self.open[i] + self.high[i] + self.low[i] + self.close[i]) / 4.0;
+            let ha_open = if i == 0 {
+                (self.open[i] + self.close[i]) / 2.0
+            } else {
+                (open[i - 1] + close[i - 1]) / 2.0
+            };
+            high.push(self.high[i].max(ha_open).max(ha_close));
+            low.push(self.low[i].min(ha_open).min(ha_close));
+            open.push(ha_open);
+            close.push(ha_close);
+        }
+        Candles {
+            id: self.id.clone(),
+            open,
+            high,
+            low,
+            close,
+            volume: self.volume.clone(),
+            time: self.time.clone(),
+            extra: self.extra.clone(),
+            labels: self.labels.clone(),
+            volume_policy: self.volume_policy,
+            instrument: self.instrument,
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+use crate::{
+    candle::{CandleRef, Candles},
+    slice::CandlesSlice,
+};
+
+/// Positions accepted by [`Candles::at`]: a `usize` borrows one row, ranges
+/// borrow a slice. `std::ops::Index` has to return a reference into the
+/// container, so it cannot hand out these views.
+pub trait CandlesIndex {
+    type Output<'a>;
+
+    fn index<'a>(self, slice: CandlesSlice<'a>) -> Option<Self::Output<'a>>;
+}
+
+impl CandlesIndex for usize {
+    type Output<'a> = CandleRef<'a>;
+
+    fn index<'a>(self, slice: CandlesSlice<'a>) -> Option<CandleRef<'a>> {
+        (self < slice.len()).then(|| CandleRef {
+            open: slice.open[self],
+            high: slice.high[self],
+            low: slice.low[self],
+            close: slice.close[self],
+            volume: slice.volume.map(|xs| xs[self]),
+            time: slice.time[self],
+            id: slice.id,
+        })
+    }
+}
+
+impl CandlesIndex for Range<usize> {
+    type Output<'a> = CandlesSlice<'a>;
+
+    fn index<'a>(self, slice: CandlesSlice<'a>) -> Option<CandlesSlice<'a>> {
+        slice.slice(self)
+    }
+}
+
+impl CandlesIndex for RangeFrom<usize> {
+    type Output<'a> = CandlesSlice<'a>;
+
+    fn index<'a>(self, slice: CandlesSlice<'a>) -> Option<CandlesSlice<'a>> {
+        slice.slice(self.start..slice.len())
+    }
+}
+
+impl CandlesIndex for RangeTo<usize> {
+    type Output<'a> = CandlesSlice<'a>;
+
+    fn index<'a>(self, slice: CandlesSlice<'a>) -> Option<CandlesSlice<'a>> {
+        slice.slice(0..self.end)
+    }
+}
+
+impl CandlesIndex for RangeInclusive<usize> {
+    type Output<'a> = CandlesSlice<'a>;
+
+    fn index<'a>(self, slice: CandlesSlice<'a>) -> Option<CandlesSlice<'a>> {
+        let end = self.end().checked_add(1)?;
+        slice.slice(*self.start()..end)
+    }
+}
+
+impl CandlesIndex for RangeToInclusive<usize> {
+    type Output<'a> = CandlesSlice<'a>;
+
+    fn index<'a>(self, slice: CandlesSlice<'a>) -> Option<CandlesSlice<'a>> {
+        slice.slice(0..self.end.checked_add(1)?)
+    }
+}
+
+impl CandlesIndex for RangeFull {
+    type Output<'a> = CandlesSlice<'a>;
+
+    fn index<'a>(self, slice: CandlesSlice<'a>) -> Option<CandlesSlice<'a>> {
+        Some(slice)
+    }
+}
+
+impl Candles {
+    /// `candles.at(5)` borrows a row and `candles.at(100..200)` a slice,
+    /// without copying; `None` when out of bounds.
+    pub fn at<I: CandlesIndex>(&self, index: I) -> Option<I::Output<'_>> {
+        index.index(self.as_slice())
+    }
+}
+
+impl<'a> CandlesSlice<'a> {
+    pub fn at<I: CandlesIndex>(&self, index: I) -> Option<I::Output<'a>> {
+        index.index(*self)
+    }
+}
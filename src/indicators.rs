@@ -0,0 +1,562 @@
+use std::collections::VecDeque;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    candle::{Candle, Candles},
+    kernels,
+    parallel::map_indices,
+};
+
+/// Output columns are aligned to the input bars, with NaN wherever the
+/// indicator is not defined yet.
+pub trait Indicator {
+    type Output;
+
+    fn compute(&self, candles: &Candles) -> Self::Output;
+}
+
+/// Per-bar counterpart of an [`Indicator`], yielding the same values as the
+/// batch version one closed bar at a time. Clone the state to evaluate a
+/// forming bar without committing it.
+pub trait IncrementalIndicator {
+    type Output;
+
+    fn update(&mut self, candle: &Candle) -> Self::Output;
+}
+
+pub fn sma(xs: &[f64], period: usize) -> Vec<f64> {
+    rolling(xs, period, |window| kernels::sum(window) / period as f64)
+}
+
+/// Applies `f` to each trailing window, NaN-padding the first `period - 1`
+/// values.
+fn rolling(xs: &[f64], period: usize, f: impl Fn(&[f64]) -> f64 + Sync + Send) -> Vec<f64> {
+    if period == 0 || xs.len() < period {
+        return vec![f64::NAN; xs.len()];
+    }
+    let mut out = vec![f64::NAN; period - 1];
+    out.extend(map_indices(
+        xs.len() - period + 1,
+        |i| f(&xs[i..i + period]),
+    ));
+    out
+}
+
+/// Seeded with the simple average of the first `period` values, skipping any
+/// leading NaNs.
+pub fn ema(xs: &[f64], period: usize) -> Vec<f64> {
+    let mut out = vec![f64::NAN; xs.len()];
+    let start = xs.iter().position(|x| !x.is_nan()).unwrap_or(xs.len());
+    if period == 0 || xs.len() - start < period {
+        return out;
+    }
+    let alpha = 2.0 / (period as f64 + 1.0);
+    let seed = start + period - 1;
+    out[seed] = xs[start..=seed].iter().sum::<f64>() / period as f64;
+    for i in seed + 1..xs.len() {
+        out[i] = alpha * xs[i] + (1.0 - alpha) * out[i - 1];
+    }
+    out
+}
+
+/// Wilder's smoothing, seeded with the simple average of the first `period`
+/// values.
+fn wilder(xs: &[f64], period: usize, start: usize) -> Vec<f64> {
+    let mut out = vec![f64::NAN; xs.len()];
+    if period == 0 || xs.len() < start + period {
+        return out;
+    }
+    let seed = start + period - 1;
+    out[seed] = xs[start..=seed].iter().sum::<f64>() / period as f64;
+    for i in seed + 1..xs.len() {
+        out[i] = (out[i - 1] * (period - 1) as f64 + xs[i]) / period as f64;
+    }
+    out
+}
+
+/// Population standard deviation of each trailing window around `mean`.
+pub(crate) fn rolling_std(xs: &[f64], mean: &[f64], period: usize) -> Vec<f64> {
+    if period == 0 || xs.len() < period {
+        return vec![f64::NAN; xs.len()];
+    }
+    let mut out = vec![f64::NAN; period - 1];
+    out.extend(map_indices(xs.len() - period + 1, |i| {
+        let mean = mean[i + period - 1];
+        let variance = xs[i..i + period]
+            .iter()
+            .map(|x| (x - mean).powi(2))
+            .sum::<f64>()
+            / period as f64;
+        variance.sqrt()
+    }));
+    out
+}
+
+pub fn true_range(candles: &Candles) -> Vec<f64> {
+    map_indices(candles.time.len(), |i| {
+        let range = candles.high[i] - candles.low[i];
+        match i.checked_sub(1).map(|prev| candles.close[prev]) {
+            Some(prev) => range
+                .max((candles.high[i] - prev).abs())
+                .max((candles.low[i] - prev).abs()),
+            None => range,
+        }
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sma {
+    pub period: usize,
+}
+
+impl Indicator for Sma {
+    type Output = Vec<f64>;
+
+    fn compute(&self, candles: &Candles) -> Vec<f64> {
+        sma(&candles.close, self.period)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ema {
+    pub period: usize,
+}
+
+impl Indicator for Ema {
+    type Output = Vec<f64>;
+
+    fn compute(&self, candles: &Candles) -> Vec<f64> {
+        ema(&candles.close, self.period)
+    }
+}
+
+/// Wilder's relative strength index over closes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rsi {
+    pub period: usize,
+}
+
+impl Indicator for Rsi {
+    type Output = Vec<f64>;
+
+    fn compute(&self, candles: &Candles) -> Vec<f64> {
+        let close = &candles.close;
+        let change = |i: usize| if i == 0 { 0.0 } else { close[i] - close[i - 1] };
+        let gains = (0..close.len())
+            .map(|i| change(i).max(0.0))
+            .collect::<Vec<_>>();
+        let losses = (0..close.len())
+            .map(|i| (-change(i)).max(0.0))
+            .collect::<Vec<_>>();
+        let gains = wilder(&gains, self.period, 1);
+        let losses = wilder(&losses, self.period, 1);
+        gains
+            .iter()
+            .zip(&losses)
+            .map(|(&gain, &loss)| {
+                if gain.is_nan() {
+                    f64::NAN
+                } else if loss == 0.0 {
+                    100.0
+                } else {
+                    100.0 - 100.0 / (1.0 + gain / loss)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Wilder's average true range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Atr {
+    pub period: usize,
+}
+
+impl Indicator for Atr {
+    type Output = Vec<f64>;
+
+    fn compute(&self, candles: &Candles) -> Vec<f64> {
+        wilder(&true_range(candles), self.period, 0)
+    }
+}
+
+/// Bands at `k` population standard deviations around the simple average of
+/// closes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BollingerBands {
+    pub period: usize,
+    pub k: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Bands {
+    pub lower: Vec<f64>,
+    pub middle: Vec<f64>,
+    pub upper: Vec<f64>,
+}
+
+impl Indicator for BollingerBands {
+    type Output = Bands;
+
+    fn compute(&self, candles: &Candles) -> Bands {
+        let middle = sma(&candles.close, self.period);
+        let deviation = rolling_std(&candles.close, &middle, self.period);
+        Bands {
+            lower: middle
+                .iter()
+                .zip(&deviation)
+                .map(|(m, d)| m - self.k * d)
+                .collect(),
+            upper: middle
+                .iter()
+                .zip(&deviation)
+                .map(|(m, d)| m + self.k * d)
+                .collect(),
+            middle,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Macd {
+    pub fast: usize,
+    pub slow: usize,
+    pub signal: usize,
+}
+
+impl Default for Macd {
+    fn default() -> Self {
+        Macd {
+            fast: 12,
+            slow: 26,
+            signal: 9,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MacdOutput {
+    pub macd: Vec<f64>,
+    pub signal: Vec<f64>,
+    pub histogram: Vec<f64>,
+}
+
+impl Indicator for Macd {
+    type Output = MacdOutput;
+
+    fn compute(&self, candles: &Candles) -> MacdOutput {
+        let fast = ema(&candles.close, self.fast);
+        let slow = ema(&candles.close, self.slow);
+        let macd = fast
+            .iter()
+            .zip(&slow)
+            .map(|(f, s)| f - s)
+            .collect::<Vec<_>>();
+        let signal = ema(&macd, self.signal);
+        let histogram = macd.iter().zip(&signal).map(|(m, s)| m - s).collect();
+        MacdOutput {
+            macd,
+            signal,
+            histogram,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SmaState {
+    period: usize,
+    window: VecDeque<f64>,
+}
+
+impl SmaState {
+    pub fn update_value(&mut self, x: f64) -> f64 {
+        if self.period == 0 {
+            return f64::NAN;
+        }
+        if self.window.len() == self.period {
+            self.window.pop_front();
+        }
+        self.window.push_back(x);
+        if self.window.len() == self.period {
+            kernels::sum(self.window.make_contiguous()) / self.period as f64
+        } else {
+            f64::NAN
+        }
+    }
+}
+
+impl Sma {
+    pub fn state(&self) -> SmaState {
+        SmaState {
+            period: self.period,
+            window: VecDeque::with_capacity(self.period),
+        }
+    }
+}
+
+impl IncrementalIndicator for SmaState {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candle) -> f64 {
+        self.update_value(candle.close)
+    }
+}
+
+/// Averages the first `period` values, then smooths with `smooth`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Seeded {
+    period: usize,
+    count: usize,
+    sum: f64,
+    value: f64,
+}
+
+impl Seeded {
+    fn new(period: usize) -> Self {
+        Seeded {
+            period,
+            count: 0,
+            sum: 0.0,
+            value: f64::NAN,
+        }
+    }
+
+    fn update(&mut self, x: f64, smooth: impl Fn(f64, f64) -> f64) -> f64 {
+        if self.period == 0 {
+            return f64::NAN;
+        }
+        if self.count < self.period {
+            self.count += 1;
+            self.sum += x;
+            if self.count == self.period {
+                self.value = self.sum / self.period as f64;
+            }
+        } else {
+            self.value = smooth(self.value, x);
+        }
+        self.value
+    }
+
+    fn ema(&mut self, x: f64) -> f64 {
+        if self.count == 0 && x.is_nan() {
+            return f64::NAN;
+        }
+        let alpha = 2.0 / (self.period as f64 + 1.0);
+        self.update(x, |prev, x| alpha * x + (1.0 - alpha) * prev)
+    }
+
+    fn wilder(&mut self, x: f64) -> f64 {
+        let period = self.period as f64;
+        self.update(x, |prev, x| (prev * (period - 1.0) + x) / period)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EmaState(Seeded);
+
+impl EmaState {
+    pub fn update_value(&mut self, x: f64) -> f64 {
+        self.0.ema(x)
+    }
+
+    pub fn value(&self) -> f64 {
+        self.0.value
+    }
+}
+
+impl Ema {
+    pub fn state(&self) -> EmaState {
+        EmaState(Seeded::new(self.period))
+    }
+}
+
+impl IncrementalIndicator for EmaState {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candle) -> f64 {
+        self.update_value(candle.close)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RsiState {
+    prev_close: Option<f64>,
+    gains: Seeded,
+    losses: Seeded,
+}
+
+impl Rsi {
+    pub fn state(&self) -> RsiState {
+        RsiState {
+            prev_close: None,
+            gains: Seeded::new(self.period),
+            losses: Seeded::new(self.period),
+        }
+    }
+}
+
+impl IncrementalIndicator for RsiState {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candle) -> f64 {
+        let Some(prev) = self.prev_close.replace(candle.close) else {
+            return f64::NAN;
+        };
+        let change = candle.close - prev;
+        let gain = self.gains.wilder(change.max(0.0));
+        let loss = self.losses.wilder((-change).max(0.0));
+        if gain.is_nan() {
+            f64::NAN
+        } else if loss == 0.0 {
+            100.0
+        } else {
+            100.0 - 100.0 / (1.0 + gain / loss)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AtrState {
+    prev_close: Option<f64>,
+    range: Seeded,
+}
+
+impl Atr {
+    pub fn state(&self) -> AtrState {
+        AtrState {
+            prev_close: None,
+            range: Seeded::new(self.period),
+        }
+    }
+}
+
+impl IncrementalIndicator for AtrState {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candle) -> f64 {
+        let range = candle.high - candle.low;
+        let range = match self.prev_close.replace(candle.close) {
+            Some(prev) => range
+                .max((candle.high - prev).abs())
+                .max((candle.low - prev).abs()),
+            None => range,
+        };
+        self.range.wilder(range)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Band {
+    pub lower: f64,
+    pub middle: f64,
+    pub upper: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BollingerState {
+    k: f64,
+    sma: SmaState,
+}
+
+impl BollingerBands {
+    pub fn state(&self) -> BollingerState {
+        BollingerState {
+            k: self.k,
+            sma: Sma {
+                period: self.period,
+            }
+            .state(),
+        }
+    }
+}
+
+impl IncrementalIndicator for BollingerState {
+    type Output = Band;
+
+    fn update(&mut self, candle: &Candle) -> Band {
+        let middle = self.sma.update_value(candle.close);
+        let deviation = if middle.is_nan() {
+            f64::NAN
+        } else {
+            let variance = self
+                .sma
+                .window
+                .iter()
+                .map(|x| (x - middle).powi(2))
+                .sum::<f64>()
+                / self.sma.period as f64;
+            variance.sqrt()
+        };
+        Band {
+            lower: middle - self.k * deviation,
+            middle,
+            upper: middle + self.k * deviation,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MacdValue {
+    pub macd: f64,
+    pub signal: f64,
+    pub histogram: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MacdState {
+    fast: EmaState,
+    slow: EmaState,
+    signal: EmaState,
+}
+
+impl Macd {
+    pub fn state(&self) -> MacdState {
+        MacdState {
+            fast: Ema { period: self.fast }.state(),
+            slow: Ema { period: self.slow }.state(),
+            signal: Ema {
+                period: self.signal,
+            }
+            .state(),
+        }
+    }
+}
+
+impl IncrementalIndicator for MacdState {
+    type Output = MacdValue;
+
+    fn update(&mut self, candle: &Candle) -> MacdValue {
+        let macd = self.fast.update(candle) - self.slow.update(candle);
+        let signal = self.signal.update_value(macd);
+        MacdValue {
+            macd,
+            signal,
+            histogram: macd - signal,
+        }
+    }
+}
+
+impl Candles {
+    pub fn indicator<I: Indicator>(&self, indicator: I) -> I::Output {
+        indicator.compute(self)
+    }
+}
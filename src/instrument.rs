@@ -0,0 +1,74 @@
+//! Price and size increments of an instrument, for rounding computed prices
+//! back onto its grid and printing them at its precision.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::candle::Candles;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InstrumentSpec {
+    /// Smallest price increment; zero or less disables tick rounding.
+    pub tick_size: f64,
+    /// Smallest size increment; zero or less disables lot rounding.
+    pub lot_size: f64,
+    /// Digits after the decimal point of quoted prices.
+    pub price_decimals: u32,
+}
+
+/// Rounds `value` to `decimals` digits, dropping the binary noise left by
+/// multiplying with a fractional step.
+fn round_decimals(value: f64, decimals: u32) -> f64 {
+    let factor = 10f64.powi(decimals.min(15) as i32);
+    (value * factor).round() / factor
+}
+
+fn round_to(value: f64, step: f64, decimals: u32) -> f64 {
+    if step > 0.0 && step.is_finite() {
+        round_decimals((value / step).round() * step, decimals)
+    } else {
+        round_decimals(value, decimals)
+    }
+}
+
+impl InstrumentSpec {
+    pub fn new(tick_size: f64, lot_size: f64, price_decimals: u32) -> Self {
+        InstrumentSpec {
+            tick_size,
+            lot_size,
+            price_decimals,
+        }
+    }
+
+    /// Nearest multiple of the tick size, at the price precision.
+    pub fn round_price(&self, price: f64) -> f64 {
+        round_to(price, self.tick_size, self.price_decimals)
+    }
+
+    /// Nearest multiple of the lot size, at the lot precision.
+    pub fn round_size(&self, size: f64) -> f64 {
+        round_to(size, self.lot_size, self.size_decimals())
+    }
+
+    /// Digits after the decimal point of the lot size, at most 15.
+    pub fn size_decimals(&self) -> u32 {
+        (0..15)
+            .find(|&decimals| {
+                let scaled = self.lot_size * 10f64.powi(decimals as i32);
+                (scaled - scaled.round()).abs() < 1e-9 * scaled.abs().max(1.0)
+            })
+            .unwrap_or(15)
+    }
+}
+
+impl Candles {
+    /// Open, high, low and close rounded with [`InstrumentSpec::round_price`];
+    /// a copy without an instrument.
+    pub fn round_to_tick(&self) -> Candles {
+        match self.instrument {
+            Some(spec) => self.map_prices(|price| spec.round_price(price)),
+            None => self.clone(),
+        }
+    }
+}
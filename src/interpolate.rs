@@ -0,0 +1,117 @@
+//! Filling NaN prices such as the bars added by [`Candles::reindex`] and
+//! [`Candles::fill_gaps`]. Only the values between the first and last known
+//! ones are interpolated; `Previous` also carries the last one forward.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::candle::Candles;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Interpolation {
+    /// Straight line in time between the neighbouring known values.
+    Linear,
+    /// The last known value.
+    Previous,
+    /// Natural cubic spline in time through every known value. Falls back to
+    /// `Linear` with fewer than three.
+    Spline,
+}
+
+/// Volume of bars whose volume is NaN.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VolumeFill {
+    #[default]
+    Zero,
+    Nan,
+}
+
+/// Natural cubic spline second derivatives at the knots, by the Thomas
+/// algorithm.
+fn second_derivatives(xs: &[f64], ys: &[f64]) -> Vec<f64> {
+    let n = xs.len();
+    let mut m = vec![0.0; n];
+    let (mut c, mut d) = (vec![0.0; n], vec![0.0; n]);
+    for i in 1..n - 1 {
+        let (h0, h1) = (xs[i] - xs[i - 1], xs[i + 1] - xs[i]);
+        let rhs = 6.0 * ((ys[i + 1] - ys[i]) / h1 - (ys[i] - ys[i - 1]) / h0);
+        let diagonal = 2.0 * (h0 + h1) - h0 * c[i - 1];
+        c[i] = h1 / diagonal;
+        d[i] = (rhs - h0 * d[i - 1]) / diagonal;
+    }
+    for i in (1..n - 1).rev() {
+        m[i] = d[i] - c[i] * m[i + 1];
+    }
+    m
+}
+
+fn fill(xs: &mut [f64], t: &[f64], method: Interpolation) {
+    let known = (0..xs.len())
+        .filter(|&i| !xs[i].is_nan())
+        .collect::<Vec<_>>();
+    let (Some(&first), Some(&last)) = (known.first(), known.last()) else {
+        return;
+    };
+    if method == Interpolation::Previous {
+        for i in first + 1..xs.len() {
+            if xs[i].is_nan() {
+                xs[i] = xs[i - 1];
+            }
+        }
+        return;
+    }
+    let kx = known.iter().map(|&i| t[i]).collect::<Vec<_>>();
+    let ky = known.iter().map(|&i| xs[i]).collect::<Vec<_>>();
+    let m = match method {
+        Interpolation::Spline if known.len() >= 3 => second_derivatives(&kx, &ky),
+        _ => vec![0.0; known.len()],
+    };
+    let mut k = 0;
+    for i in first..=last {
+        if !xs[i].is_nan() {
+            continue;
+        }
+        while known[k + 1] < i {
+            k += 1;
+        }
+        let h = kx[k + 1] - kx[k];
+        let (a, b) = ((kx[k + 1] - t[i]) / h, (t[i] - kx[k]) / h);
+        xs[i] = a * ky[k]
+            + b * ky[k + 1]
+            + ((a.powi(3) - a) * m[k] + (b.powi(3) - b) * m[k + 1]) * h * h / 6.0;
+    }
+}
+
+impl Candles {
+    /// Fills NaN prices column by column with `method` and NaN volume with
+    /// `volume`. Filled bars are then widened so the high and low enclose the
+    /// open and close.
+    pub fn interpolate(&self, method: Interpolation, volume: VolumeFill) -> Candles {
+        let Some(&start) = self.time.first() else {
+            return self.clone();
+        };
+        let t = self
+            .time
+            .iter()
+            .map(|time| (*time - start).num_milliseconds() as f64)
+            .collect::<Vec<_>>();
+        let mut out = self.clone();
+        for xs in [&mut out.open, &mut out.high, &mut out.low, &mut out.close] {
+            fill(xs, &t, method);
+        }
+        for i in 0..self.time.len() {
+            if self.high[i].is_nan() || self.low[i].is_nan() {
+                out.high[i] = out.high[i].max(out.open[i]).max(out.close[i]);
+                out.low[i] = out.low[i].min(out.open[i]).min(out.close[i]);
+            }
+        }
+        if let (Some(xs), VolumeFill::Zero) = (out.volume.as_mut(), volume) {
+            for x in xs.iter_mut().filter(|x| x.is_nan()) {
+                *x = 0.0;
+            }
+        }
+        out
+    }
+}
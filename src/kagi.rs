@@ -0,0 +1,127 @@
+use chrono::{DateTime, Utc};
+
+use crate::{candle::Candles, symbol::Symbol};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KagiThickness {
+    /// Thick line, after the price broke above the previous shoulder.
+    Yang,
+    /// Thin line, after the price broke below the previous waist.
+    Yin,
+}
+
+/// Vertical kagi line from `start` to `end`. The thickness is the one the
+/// line ends with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KagiLine {
+    pub start: f64,
+    pub end: f64,
+    pub thickness: KagiThickness,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+}
+
+impl KagiLine {
+    pub fn is_rising(&self) -> bool {
+        self.end > self.start
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct KagiChart {
+    pub id: Symbol,
+    pub reversal: f64,
+    pub lines: Vec<KagiLine>,
+}
+
+impl KagiChart {
+    /// One pseudo-candle per line stamped with its start, for plotting.
+    pub fn to_candles(&self) -> Candles {
+        let mut candles = Candles::with_capacity(&self.id, self.lines.len(), false);
+        for line in &self.lines {
+            candles.push_row(
+                line.start,
+                line.start.max(line.end),
+                line.start.min(line.end),
+                line.end,
+                None,
+                line.start_time,
+            );
+        }
+        candles
+    }
+}
+
+impl Candles {
+    /// Kagi chart of the closes: a line keeps going while the price moves
+    /// its way and turns once it retraces `reversal` from the line's end.
+    /// `None` unless `reversal` is positive.
+    pub fn to_kagi(&self, reversal: f64) -> Option<KagiChart> {
+        if reversal.is_nan() || reversal <= 0.0 {
+            return None;
+        }
+        let mut lines: Vec<KagiLine> = Vec::new();
+        let mut thickness = KagiThickness::Yang;
+        let mut shoulder = f64::INFINITY;
+        let mut waist = f64::NEG_INFINITY;
+        let Some((&start, &start_time)) = self.close.first().zip(self.time.first()) else {
+            return Some(KagiChart {
+                id: self.id.clone(),
+                reversal,
+                lines,
+            });
+        };
+        for (&price, &time) in self.close.iter().zip(&self.time).skip(1) {
+            let Some(line) = lines.last_mut() else {
+                if (price - start).abs() >= reversal {
+                    thickness = if price > start {
+                        KagiThickness::Yang
+                    } else {
+                        KagiThickness::Yin
+                    };
+                    lines.push(KagiLine {
+                        start,
+                        end: price,
+                        thickness,
+                        start_time,
+                        end_time: time,
+                    });
+                }
+                continue;
+            };
+            let rising = line.is_rising();
+            if (rising && price > line.end) || (!rising && price < line.end) {
+                line.end = price;
+                line.end_time = time;
+            } else if (line.end - price).abs() >= reversal {
+                if rising {
+                    shoulder = line.end;
+                } else {
+                    waist = line.end;
+                }
+                let start = line.end;
+                lines.push(KagiLine {
+                    start,
+                    end: price,
+                    thickness,
+                    start_time: time,
+                    end_time: time,
+                });
+            } else {
+                continue;
+            }
+            let line = lines.last_mut().unwrap();
+            if line.end > shoulder {
+                thickness = KagiThickness::Yang;
+            } else if line.end < waist {
+                thickness = KagiThickness::Yin;
+            }
+            line.thickness = thickness;
+        }
+        Some(KagiChart {
+            id: self.id.clone(),
+            reversal,
+            lines,
+        })
+    }
+}
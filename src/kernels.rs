@@ -0,0 +1,94 @@
+//! Column reductions written over fixed-width lanes so the compiler can
+//! vectorize them on stable Rust; the tail that does not fill a lane is
+//! reduced with the plain scalar loop. Lane-wise sums add in a different
+//! order than a sequential loop and may differ in the last bits.
+
+use std::collections::VecDeque;
+
+const LANES: usize = 8;
+
+fn reduce(xs: &[f64], init: f64, f: impl Fn(f64, f64) -> f64) -> f64 {
+    let mut acc = [init; LANES];
+    let chunks = xs.chunks_exact(LANES);
+    let tail = chunks.remainder();
+    for chunk in chunks {
+        for (acc, &x) in acc.iter_mut().zip(chunk) {
+            *acc = f(*acc, x);
+        }
+    }
+    let acc = acc.into_iter().fold(init, &f);
+    tail.iter().fold(acc, |acc, &x| f(acc, x))
+}
+
+/// NaNs propagate.
+pub fn sum(xs: &[f64]) -> f64 {
+    reduce(xs, 0.0, |a, b| a + b)
+}
+
+/// NaNs propagate.
+pub fn dot(xs: &[f64], ys: &[f64]) -> f64 {
+    let len = xs.len().min(ys.len());
+    let offset = len - len % LANES;
+    let mut acc = [0.0; LANES];
+    for (x, y) in xs[..offset]
+        .chunks_exact(LANES)
+        .zip(ys[..offset].chunks_exact(LANES))
+    {
+        for i in 0..LANES {
+            acc[i] += x[i] * y[i];
+        }
+    }
+    let acc = acc.into_iter().sum::<f64>();
+    acc + xs[offset..len]
+        .iter()
+        .zip(&ys[offset..len])
+        .map(|(x, y)| x * y)
+        .sum::<f64>()
+}
+
+/// NaNs are ignored; an empty or all-NaN column gives negative infinity.
+pub fn max(xs: &[f64]) -> f64 {
+    reduce(xs, f64::NEG_INFINITY, f64::max)
+}
+
+/// NaNs are ignored; an empty or all-NaN column gives infinity.
+pub fn min(xs: &[f64]) -> f64 {
+    reduce(xs, f64::INFINITY, f64::min)
+}
+
+/// Maximum of each trailing window in `O(n)`, NaN-padding the first
+/// `period - 1` values.
+pub fn rolling_max(xs: &[f64], period: usize) -> Vec<f64> {
+    rolling_extreme(xs, period, |a, b| a >= b)
+}
+
+/// Minimum of each trailing window in `O(n)`, NaN-padding the first
+/// `period - 1` values.
+pub fn rolling_min(xs: &[f64], period: usize) -> Vec<f64> {
+    rolling_extreme(xs, period, |a, b| a <= b)
+}
+
+/// Monotonic deque of candidate indices; `dominates(a, b)` keeps `a` over an
+/// older `b`.
+fn rolling_extreme(xs: &[f64], period: usize, dominates: fn(f64, f64) -> bool) -> Vec<f64> {
+    let mut out = vec![f64::NAN; xs.len()];
+    if period == 0 {
+        return out;
+    }
+    let mut deque = VecDeque::with_capacity(period);
+    for (i, &x) in xs.iter().enumerate() {
+        if deque.front().is_some_and(|&front| front + period <= i) {
+            deque.pop_front();
+        }
+        if !x.is_nan() {
+            while deque.back().is_some_and(|&back| dominates(x, xs[back])) {
+                deque.pop_back();
+            }
+            deque.push_back(i);
+        }
+        if i + 1 >= period {
+            out[i] = deque.front().map_or(f64::NAN, |&front| xs[front]);
+        }
+    }
+    out
+}
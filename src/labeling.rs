@@ -0,0 +1,104 @@
+//! Forward-looking targets for supervised learning. Every label uses bars
+//! after its own, so never feed them back as features.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{candle::Candles, parallel::map_indices};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Barrier {
+    /// Profit take.
+    Upper,
+    /// Stop loss.
+    Lower,
+    /// Holding period ran out.
+    Vertical,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BarrierLabel {
+    /// 1 for the upper barrier, -1 for the lower and the sign of the return
+    /// for the vertical one.
+    pub label: i8,
+    pub barrier: Barrier,
+    /// Index of the bar that touched the barrier.
+    pub exit: usize,
+    /// Close-to-barrier return, or close-to-close for the vertical barrier.
+    pub ret: f64,
+}
+
+fn sign(x: f64) -> i8 {
+    if x > 0.0 {
+        1
+    } else if x < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+impl Candles {
+    /// Triple-barrier labels with entries at each close, barriers at `pt`
+    /// above and `sl` below it as fractions, and a vertical barrier
+    /// `max_holding` bars later. A bar touching both barriers counts as the
+    /// stop. A non-positive or NaN `pt` or `sl` disables that barrier. `None`
+    /// where no barrier is touched before the data ends.
+    pub fn triple_barrier_labels(
+        &self,
+        pt: f64,
+        sl: f64,
+        max_holding: usize,
+    ) -> Vec<Option<BarrierLabel>> {
+        let len = self.time.len();
+        map_indices(len, |i| {
+            let entry = self.close[i];
+            let upper = (pt > 0.0).then_some(entry * (1.0 + pt));
+            let lower = (sl > 0.0).then_some(entry * (1.0 - sl));
+            let horizon = i.checked_add(max_holding)?;
+            for j in i + 1..=horizon.min(len - 1) {
+                if let Some(lower) = lower.filter(|lower| self.low[j] <= *lower) {
+                    return Some(BarrierLabel {
+                        label: -1,
+                        barrier: Barrier::Lower,
+                        exit: j,
+                        ret: lower / entry - 1.0,
+                    });
+                }
+                if let Some(upper) = upper.filter(|upper| self.high[j] >= *upper) {
+                    return Some(BarrierLabel {
+                        label: 1,
+                        barrier: Barrier::Upper,
+                        exit: j,
+                        ret: upper / entry - 1.0,
+                    });
+                }
+            }
+            (horizon < len && max_holding > 0).then(|| {
+                let ret = self.close[horizon] / entry - 1.0;
+                BarrierLabel {
+                    label: sign(ret),
+                    barrier: Barrier::Vertical,
+                    exit: horizon,
+                    ret,
+                }
+            })
+        })
+    }
+
+    /// Sign of the close-to-close return `horizon` bars ahead: 1, -1 or 0,
+    /// NaN for the last `horizon` bars.
+    pub fn fixed_horizon_labels(&self, horizon: usize) -> Vec<f64> {
+        let len = self.time.len();
+        (0..len)
+            .map(
+                |i| match i.checked_add(horizon).filter(|j| *j < len && horizon > 0) {
+                    Some(j) => f64::from(sign(self.close[j] / self.close[i] - 1.0)),
+                    None => f64::NAN,
+                },
+            )
+            .collect()
+    }
+}
@@ -1,3 +1,107 @@
+//! Columnar OHLCV candles. Times are `chrono::DateTime<Utc>` throughout, so
+//! chrono is a required dependency whatever features are enabled; only
+//! formats and integrations are optional. `erfurt-core` is a standalone
+//! `no_std`, chrono-free subset with i64 times, converted to and from with
+//! the `erfurt-core` feature.
+
 #![allow(dead_code)]
+pub mod adjust;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "backfill")]
+pub mod backfill;
+pub mod bars;
+#[cfg(feature = "binance")]
+pub mod binance;
+pub mod binary;
+#[cfg(any(feature = "msgpack", feature = "bincode"))]
+pub mod blob;
+pub mod bounded;
+pub mod builder;
+pub mod cache;
 pub mod candle;
+pub mod causal;
+pub mod combine;
+#[cfg(feature = "zstd")]
+pub mod compress;
+pub mod cursor;
+#[cfg(feature = "decimal")]
+pub mod decimal;
+pub mod delta;
+pub mod derive;
+pub mod diff;
+pub mod drawdown;
+pub mod edit;
+pub mod error;
+#[cfg(feature = "exchanges")]
+pub mod exchanges;
+pub mod extra;
+pub mod field;
+pub mod gaps;
+pub mod hash;
+pub mod heikin_ashi;
+pub mod index;
+pub mod indicators;
+pub mod instrument;
+pub mod interpolate;
+pub mod kagi;
+pub mod kernels;
+pub mod labeling;
+pub mod line_break;
+pub mod map;
+#[cfg(any(feature = "ndarray", feature = "nalgebra"))]
+pub mod matrix;
+pub mod merge;
+#[cfg(feature = "ndjson")]
+pub mod ndjson;
+pub mod normalize;
+#[cfg(feature = "erfurt-core")]
+pub mod nostd;
+pub mod outliers;
+pub mod parallel;
+pub mod partition;
+pub mod patterns;
+#[cfg(feature = "plot")]
+pub mod plot;
+pub mod pnf;
+#[cfg(feature = "polars")]
+pub mod polars;
 pub mod prelude;
+pub mod prices;
+pub mod profile;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod quality;
+pub mod reconcile;
+pub mod reindex;
+pub mod replay;
+pub mod resample;
+#[cfg(feature = "serde")]
+pub mod rows;
+pub mod sample;
+#[cfg(feature = "serde")]
+pub mod schema;
+pub mod session;
+pub mod shift;
+pub mod slice;
+pub mod snapshot;
+pub mod sort;
+pub mod source;
+pub mod stats;
+#[cfg(feature = "storage")]
+pub mod storage;
+#[cfg(feature = "async")]
+pub mod stream;
+pub mod symbol;
+pub mod synthetic;
+pub mod table;
+pub mod timeframe;
+pub mod timestamp;
+pub mod validation;
+pub mod volatility;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod window;
+pub mod zoned;
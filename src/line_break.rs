@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+
+use crate::{candle::Candles, symbol::Symbol};
+
+/// Block of a line break chart from `open` to `close`, drawn at the bar
+/// that completed it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineBreakBlock {
+    pub open: f64,
+    pub close: f64,
+    pub time: DateTime<Utc>,
+}
+
+impl LineBreakBlock {
+    pub fn is_rising(&self) -> bool {
+        self.close > self.open
+    }
+
+    fn high(&self) -> f64 {
+        self.open.max(self.close)
+    }
+
+    fn low(&self) -> f64 {
+        self.open.min(self.close)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineBreakChart {
+    pub id: Symbol,
+    pub lines: usize,
+    pub blocks: Vec<LineBreakBlock>,
+}
+
+impl LineBreakChart {
+    /// One pseudo-candle per block, for plotting.
+    pub fn to_candles(&self) -> Candles {
+        let mut candles = Candles::with_capacity(&self.id, self.blocks.len(), false);
+        for block in &self.blocks {
+            candles.push_row(
+                block.open,
+                block.high(),
+                block.low(),
+                block.close,
+                None,
+                block.time,
+            );
+        }
+        candles
+    }
+}
+
+impl Candles {
+    /// `lines`-line break chart of the closes. A block continues the trend
+    /// when the close passes the previous block's close and reverses it only
+    /// when the close passes the extreme of the last `lines` blocks. `None`
+    /// for zero lines.
+    pub fn to_line_break(&self, lines: usize) -> Option<LineBreakChart> {
+        if lines == 0 {
+            return None;
+        }
+        let mut blocks: Vec<LineBreakBlock> = Vec::new();
+        let Some(&reference) = self.close.first() else {
+            return Some(LineBreakChart {
+                id: self.id.clone(),
+                lines,
+                blocks,
+            });
+        };
+        for (&price, &time) in self.close.iter().zip(&self.time).skip(1) {
+            let Some(last) = blocks.last() else {
+                if price != reference {
+                    blocks.push(LineBreakBlock {
+                        open: reference,
+                        close: price,
+                        time,
+                    });
+                }
+                continue;
+            };
+            let recent = &blocks[blocks.len().saturating_sub(lines)..];
+            let low = recent
+                .iter()
+                .map(LineBreakBlock::low)
+                .fold(f64::INFINITY, f64::min);
+            let high = recent
+                .iter()
+                .map(LineBreakBlock::high)
+                .fold(f64::NEG_INFINITY, f64::max);
+            let open = match last.is_rising() {
+                true if price > last.close => Some(last.close),
+                true if price < low => Some(last.open),
+                false if price < last.close => Some(last.close),
+                false if price > high => Some(last.open),
+                _ => None,
+            };
+            if let Some(open) = open {
+                blocks.push(LineBreakBlock {
+                    open,
+                    close: price,
+                    time,
+                });
+            }
+        }
+        Some(LineBreakChart {
+            id: self.id.clone(),
+            lines,
+            blocks,
+        })
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    candle::{Candle, Candles, CandlesExt},
+    symbol::Symbol,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Align {
+    /// Keep only timestamps present in every series.
+    Inner,
+    /// Keep every timestamp, forward-filling missing bars with a flat candle at
+    /// the previous close and zero volume. Bars before a series starts are NaN.
+    Outer,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CandlesMap {
+    candles: BTreeMap<Symbol, Candles>,
+}
+
+impl CandlesMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, candles: Candles) -> Option<Candles> {
+        self.candles.insert(candles.id.clone(), candles)
+    }
+
+    pub fn get(&self, symbol: &str) -> Option<&Candles> {
+        self.candles.get(symbol)
+    }
+
+    pub fn get_mut(&mut self, symbol: &str) -> Option<&mut Candles> {
+        self.candles.get_mut(symbol)
+    }
+
+    /// Applies `f` to every series in parallel, keyed by symbol.
+    #[cfg(feature = "rayon")]
+    pub fn par_map<T: Send>(&self, f: impl Fn(&Candles) -> T + Sync + Send) -> BTreeMap<Symbol, T> {
+        use rayon::prelude::*;
+
+        self.candles
+            .par_iter()
+            .map(|(symbol, candles)| (symbol.clone(), f(candles)))
+            .collect()
+    }
+
+    pub fn remove(&mut self, symbol: &str) -> Option<Candles> {
+        self.candles.remove(symbol)
+    }
+
+    pub fn len(&self) -> usize {
+        self.candles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.candles.is_empty()
+    }
+
+    pub fn symbols(&self) -> impl Iterator<Item = &str> {
+        self.candles.keys().map(Symbol::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Candles)> {
+        self.candles
+            .iter()
+            .map(|(symbol, candles)| (symbol.as_str(), candles))
+    }
+
+    fn time_index(&self, how: Align) -> Vec<DateTime<Utc>> {
+        let mut times = self
+            .candles
+            .values()
+            .flat_map(|candles| candles.time.iter().copied())
+            .collect::<Vec<_>>();
+        times.sort_unstable();
+        times.dedup();
+        if how == Align::Inner {
+            times.retain(|time| {
+                self.candles
+                    .values()
+                    .all(|candles| candles.time.binary_search(time).is_ok())
+            });
+        }
+        times
+    }
+
+    pub fn align(&self, how: Align) -> CandlesMap {
+        let times = self.time_index(how);
+        let candles = self
+            .candles
+            .iter()
+            .map(|(symbol, candles)| {
+                let mut aligned = candles.empty_like();
+                let mut i = 0;
+                for &time in &times {
+                    while i < candles.time.len() && candles.time[i] < time {
+                        i += 1;
+                    }
+                    if i < candles.time.len() && candles.time[i] == time {
+                        aligned.push_index(candles, i);
+                    } else if i > 0 {
+                        let close = candles.close[i - 1];
+                        aligned.push_row(close, close, close, close, Some(0.0), time);
+                    } else {
+                        let nan = f64::NAN;
+                        aligned.push_row(nan, nan, nan, nan, Some(nan), time);
+                    }
+                }
+                (symbol.clone(), aligned)
+            })
+            .collect();
+        CandlesMap { candles }
+    }
+
+    /// Bars grouped by timestamp across all series, in symbol order. Series
+    /// without a bar at a given time are left out of that cross-section.
+    pub fn cross_sections(&self) -> CrossSections<'_> {
+        CrossSections {
+            cursors: self.candles.values().map(|candles| (candles, 0)).collect(),
+        }
+    }
+}
+
+impl FromIterator<Candles> for CandlesMap {
+    fn from_iter<I: IntoIterator<Item = Candles>>(iter: I) -> Self {
+        let mut map = CandlesMap::new();
+        for candles in iter {
+            map.insert(candles);
+        }
+        map
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CrossSections<'a> {
+    cursors: Vec<(&'a Candles, usize)>,
+}
+
+impl Iterator for CrossSections<'_> {
+    type Item = (DateTime<Utc>, Vec<Candle>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let time = self
+            .cursors
+            .iter()
+            .filter_map(|(candles, i)| candles.time.get(*i))
+            .min()
+            .copied()?;
+        let mut section = Vec::new();
+        for (candles, i) in self.cursors.iter_mut() {
+            if candles.time.get(*i) == Some(&time) {
+                section.extend(candles.get(*i));
+                *i += 1;
+            }
+        }
+        Some((time, section))
+    }
+}
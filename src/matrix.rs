@@ -0,0 +1,118 @@
+//! Dense matrices with one row per bar and the columns open, high, low,
+//! close and, when present, volume. Extra columns are not carried.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+use crate::{candle::Candles, symbol::Symbol};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MatrixError {
+    /// The matrix needs 4 columns without volume or 5 with it.
+    Columns {
+        found: usize,
+    },
+    Rows {
+        times: usize,
+        rows: usize,
+    },
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatrixError::Columns { found } => {
+                write!(f, "expected 4 or 5 columns, found {found}")
+            }
+            MatrixError::Rows { times, rows } => {
+                write!(f, "{times} timestamps for {rows} rows")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MatrixError {}
+
+impl Candles {
+    fn matrix_columns(&self) -> usize {
+        if self.volume.is_some() {
+            5
+        } else {
+            4
+        }
+    }
+
+    fn matrix_value(&self, row: usize, column: usize) -> f64 {
+        match column {
+            0 => self.open[row],
+            1 => self.high[row],
+            2 => self.low[row],
+            3 => self.close[row],
+            _ => self.volume.as_ref().map_or(f64::NAN, |xs| xs[row]),
+        }
+    }
+
+    fn from_matrix(
+        id: Symbol,
+        time: Vec<DateTime<Utc>>,
+        shape: (usize, usize),
+        value: impl Fn(usize, usize) -> f64,
+    ) -> Result<Candles, MatrixError> {
+        let (rows, columns) = shape;
+        if columns != 4 && columns != 5 {
+            return Err(MatrixError::Columns { found: columns });
+        }
+        if time.len() != rows {
+            return Err(MatrixError::Rows {
+                times: time.len(),
+                rows,
+            });
+        }
+        let column = |j: usize| (0..rows).map(|i| value(i, j)).collect::<Vec<_>>();
+        Ok(Candles {
+            id,
+            open: column(0),
+            high: column(1),
+            low: column(2),
+            close: column(3),
+            volume: (columns == 5).then(|| column(4)),
+            time,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl Candles {
+    pub fn to_ndarray(&self) -> ndarray::Array2<f64> {
+        ndarray::Array2::from_shape_fn((self.time.len(), self.matrix_columns()), |(i, j)| {
+            self.matrix_value(i, j)
+        })
+    }
+
+    pub fn from_ndarray(
+        id: impl Into<Symbol>,
+        time: Vec<DateTime<Utc>>,
+        array: ndarray::ArrayView2<'_, f64>,
+    ) -> Result<Candles, MatrixError> {
+        Candles::from_matrix(id.into(), time, array.dim(), |i, j| array[(i, j)])
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl Candles {
+    pub fn to_dmatrix(&self) -> nalgebra::DMatrix<f64> {
+        nalgebra::DMatrix::from_fn(self.time.len(), self.matrix_columns(), |i, j| {
+            self.matrix_value(i, j)
+        })
+    }
+
+    pub fn from_dmatrix(
+        id: impl Into<Symbol>,
+        time: Vec<DateTime<Utc>>,
+        matrix: &nalgebra::DMatrix<f64>,
+    ) -> Result<Candles, MatrixError> {
+        Candles::from_matrix(id.into(), time, matrix.shape(), |i, j| matrix[(i, j)])
+    }
+}
@@ -0,0 +1,179 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    candle::{Candles, VolumePolicy},
+    symbol::Symbol,
+};
+
+/// How to resolve bars present in both series. `other` is treated as the newer data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergePolicy {
+    PreferNewer,
+    PreferOlder,
+    ErrorOnConflict,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MergeError {
+    IdMismatch {
+        expected: Symbol,
+        found: Symbol,
+    },
+    VolumeMismatch,
+    Conflict {
+        time: DateTime<Utc>,
+    },
+    Overlap {
+        last: DateTime<Utc>,
+        first: DateTime<Utc>,
+    },
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeError::IdMismatch { expected, found } => {
+                write!(f, "series id {found} does not match {expected}")
+            }
+            MergeError::VolumeMismatch => {
+                write!(f, "only one of the series has a volume column")
+            }
+            MergeError::Conflict { time } => write!(f, "conflicting bars at {time}"),
+            MergeError::Overlap { last, first } => {
+                write!(f, "appended series starts at {first}, not after {last}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+impl Candles {
+    pub(crate) fn check_compatible(&self, other: &Candles) -> Result<(), MergeError> {
+        if self.id != other.id {
+            return Err(MergeError::IdMismatch {
+                expected: self.id.clone(),
+                found: other.id.clone(),
+            });
+        }
+        let mismatch = match self.volume_policy {
+            VolumePolicy::Absent => false,
+            VolumePolicy::Required => other.volume.is_none() && !other.is_empty(),
+            VolumePolicy::Optional => {
+                self.volume.is_some() != other.volume.is_some()
+                    && !self.is_empty()
+                    && !other.is_empty()
+            }
+        };
+        if mismatch {
+            return Err(MergeError::VolumeMismatch);
+        }
+        Ok(())
+    }
+
+    /// True when both bars hold the same values, counting NaN as equal to NaN.
+    fn same_bar(&self, i: usize, other: &Candles, j: usize) -> bool {
+        let same = |a: f64, b: f64| a == b || (a.is_nan() && b.is_nan());
+        let volume = match (&self.volume, &other.volume) {
+            (Some(xs), Some(ys)) => same(xs[i], ys[j]),
+            (xs, ys) => xs.is_none() && ys.is_none(),
+        };
+        same(self.open[i], other.open[j])
+            && same(self.high[i], other.high[j])
+            && same(self.low[i], other.low[j])
+            && same(self.close[i], other.close[j])
+            && volume
+    }
+
+    /// The merged series has volume if a side with rows has it, or if either
+    /// has it when neither or both have rows.
+    fn merged_has_volume(&self, other: &Candles) -> bool {
+        match (self.is_empty(), other.is_empty()) {
+            (true, false) => other.volume.is_some(),
+            (false, true) => self.volume.is_some(),
+            _ => self.volume.is_some() || other.volume.is_some(),
+        }
+    }
+
+    pub fn merge(&self, other: &Candles, policy: MergePolicy) -> Result<Candles, MergeError> {
+        self.check_compatible(other)?;
+        let mut merged = self.empty_like();
+        if self.volume_policy != VolumePolicy::Absent {
+            merged.volume = self.merged_has_volume(other).then(Vec::new);
+        }
+        for (name, _) in other.extra.iter() {
+            if !merged.extra.contains(name) {
+                merged.extra.insert(name, Vec::new());
+            }
+        }
+        if merged.labels.is_none() {
+            merged.labels = other.labels.as_ref().map(|_| Vec::new());
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < self.time.len() || j < other.time.len() {
+            if j == other.time.len() || (i < self.time.len() && self.time[i] < other.time[j]) {
+                merged.push_index(self, i);
+                i += 1;
+            } else if i == self.time.len() || other.time[j] < self.time[i] {
+                merged.push_index(other, j);
+                j += 1;
+            } else {
+                match policy {
+                    MergePolicy::PreferNewer => merged.push_index(other, j),
+                    MergePolicy::PreferOlder => merged.push_index(self, i),
+                    MergePolicy::ErrorOnConflict if self.same_bar(i, other, j) => {
+                        merged.push_index(self, i)
+                    }
+                    MergePolicy::ErrorOnConflict => {
+                        return Err(MergeError::Conflict { time: self.time[i] })
+                    }
+                }
+                i += 1;
+                j += 1;
+            }
+        }
+        Ok(merged)
+    }
+
+    pub fn append(&mut self, other: &Candles) -> Result<(), MergeError> {
+        self.check_compatible(other)?;
+        if let (Some(last), Some(first)) = (self.time.last(), other.time.first()) {
+            if first <= last {
+                return Err(MergeError::Overlap {
+                    last: *last,
+                    first: *first,
+                });
+            }
+        }
+        if self.is_empty() {
+            if self.volume_policy == VolumePolicy::Optional {
+                self.volume = other.volume.as_ref().map(|_| Vec::new());
+            }
+            self.extra = other.extra.empty_like();
+            self.labels = other.labels.as_ref().map(|_| Vec::new());
+        }
+        self.open.extend_from_slice(&other.open);
+        self.high.extend_from_slice(&other.high);
+        self.low.extend_from_slice(&other.low);
+        self.close.extend_from_slice(&other.close);
+        if let Some(volume) = self.volume.as_mut() {
+            volume.extend(other.volume.iter().flatten().copied());
+        }
+        self.time.extend_from_slice(&other.time);
+        for (name, xs) in self.extra.iter_mut() {
+            match other.extra.get(name) {
+                Some(ys) => xs.extend_from_slice(ys),
+                None => xs.resize(xs.len() + other.time.len(), f64::NAN),
+            }
+        }
+        if let Some(xs) = self.labels.as_mut() {
+            match &other.labels {
+                Some(ys) => xs.extend_from_slice(ys),
+                None => xs.resize(xs.len() + other.time.len(), 0),
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use std::{
+    fmt,
+    io::{BufReader, BufWriter, Read, Write},
+};
+
+use crate::candle::{Candle, Candles, PushError};
+
+#[derive(Debug)]
+pub enum NdjsonError {
+    Json(serde_json::Error),
+    Push(PushError),
+}
+
+impl fmt::Display for NdjsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NdjsonError::Json(err) => write!(f, "{err}"),
+            NdjsonError::Push(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for NdjsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            NdjsonError::Json(err) => Some(err),
+            NdjsonError::Push(err) => Some(err),
+        }
+    }
+}
+
+impl From<serde_json::Error> for NdjsonError {
+    fn from(err: serde_json::Error) -> Self {
+        NdjsonError::Json(err)
+    }
+}
+
+impl From<PushError> for NdjsonError {
+    fn from(err: PushError) -> Self {
+        NdjsonError::Push(err)
+    }
+}
+
+impl Candles {
+    /// Reads one candle per line. The reader is buffered internally.
+    pub fn from_ndjson<R: Read>(reader: R) -> Result<Candles, NdjsonError> {
+        let mut candles = Candles::default();
+        let reader = BufReader::new(reader);
+        for candle in serde_json::Deserializer::from_reader(reader).into_iter::<Candle>() {
+            candles.try_push(candle?)?;
+        }
+        Ok(candles)
+    }
+
+    /// Writes one candle per line. The writer is buffered internally.
+    pub fn write_ndjson<W: Write>(&self, writer: W) -> Result<(), serde_json::Error> {
+        let mut writer = BufWriter::new(writer);
+        for candle in self.iter() {
+            serde_json::to_writer(&mut writer, &candle)?;
+            writer.write_all(b"\n").map_err(serde_json::Error::io)?;
+        }
+        writer.flush().map_err(serde_json::Error::io)
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    candle::Candles,
+    indicators::{rolling_std, sma, Atr, Indicator},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Normalization {
+    /// Distance from the trailing mean of closes in trailing standard
+    /// deviations, NaN until `window` bars are available.
+    ZScore { window: usize },
+    /// Scaled to `[0, 1]` by the lowest low and highest high of the whole
+    /// series, which looks ahead.
+    MinMax,
+    /// Distance from the previous close in units of the current ATR.
+    AtrRelative { period: usize },
+}
+
+impl Candles {
+    /// Transforms the price columns; volume and extra columns are kept as
+    /// they are.
+    pub fn normalize(&self, normalization: Normalization) -> Candles {
+        let len = self.time.len();
+        let (center, scale): (Vec<f64>, Vec<f64>) = match normalization {
+            Normalization::ZScore { window } => {
+                let mean = sma(&self.close, window);
+                let deviation = rolling_std(&self.close, &mean, window);
+                (mean, deviation)
+            }
+            Normalization::MinMax => {
+                let low = self.low.iter().copied().fold(f64::INFINITY, f64::min);
+                let high = self.high.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                (vec![low; len], vec![high - low; len])
+            }
+            Normalization::AtrRelative { period } => {
+                let prev = (0..len)
+                    .map(|i| i.checked_sub(1).map_or(f64::NAN, |prev| self.close[prev]))
+                    .collect();
+                (prev, Atr { period }.compute(self))
+            }
+        };
+        let transform = |xs: &[f64]| {
+            xs.iter()
+                .zip(center.iter().zip(&scale))
+                .map(|(x, (center, scale))| (x - center) / scale)
+                .collect()
+        };
+        Candles {
+            id: self.id.clone(),
+            open: transform(&self.open),
+            high: transform(&self.high),
+            low: transform(&self.low),
+            close: transform(&self.close),
+            volume: self.volume.clone(),
+            time: self.time.clone(),
+            extra: self.extra.clone(),
+            labels: self.labels.clone(),
+            volume_policy: self.volume_policy,
+            instrument: None,
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Conversions to and from the `no_std` types of `erfurt-core`, whose
+//! timestamps are taken as nanoseconds since the Unix epoch. Extra columns
+//! and labels have no counterpart there and are dropped.
+
+use chrono::DateTime;
+
+use crate::{
+    candle::{Candle, Candles},
+    symbol::Symbol,
+    timestamp::{EpochNanos, TimestampError},
+};
+
+impl Candle {
+    pub fn from_core(id: impl Into<Symbol>, candle: &erfurt_core::Candle) -> Candle {
+        Candle {
+            open: candle.open,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+            volume: candle.volume,
+            time: DateTime::from_timestamp_nanos(candle.time),
+            id: id.into(),
+        }
+    }
+
+    pub fn to_core(&self) -> Result<erfurt_core::Candle, TimestampError> {
+        Ok(erfurt_core::Candle {
+            open: self.open,
+            high: self.high,
+            low: self.low,
+            close: self.close,
+            volume: self.volume,
+            time: self.time_as::<EpochNanos>()?.0,
+        })
+    }
+}
+
+impl Candles {
+    pub fn from_core(id: impl Into<Symbol>, candles: &erfurt_core::Candles) -> Candles {
+        Candles {
+            id: id.into(),
+            open: candles.open.clone(),
+            high: candles.high.clone(),
+            low: candles.low.clone(),
+            close: candles.close.clone(),
+            volume: candles.volume.clone(),
+            time: candles
+                .time
+                .iter()
+                .map(|&nanos| DateTime::from_timestamp_nanos(nanos))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn to_core(&self) -> Result<erfurt_core::Candles, TimestampError> {
+        Ok(erfurt_core::Candles {
+            open: self.open.clone(),
+            high: self.high.clone(),
+            low: self.low.clone(),
+            close: self.close.clone(),
+            volume: self.volume.clone(),
+            time: self
+                .time_as::<EpochNanos>()?
+                .into_iter()
+                .map(|nanos| nanos.0)
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::candle::Candles;
+
+/// The high, low and close of each bar are compared against the median of the
+/// same column over up to `window` bars centred on it, not counting the bar
+/// itself.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OutlierMethod {
+    /// Flags bars more than `threshold` median absolute deviations away
+    /// from the median.
+    MedianFilter { window: usize, threshold: f64 },
+    /// Flags bars more than `fraction` of the median away from it, which
+    /// also works on flat stretches.
+    MaxDeviation { window: usize, fraction: f64 },
+}
+
+fn median(xs: &mut [f64]) -> f64 {
+    if xs.is_empty() {
+        return f64::NAN;
+    }
+    xs.sort_unstable_by(f64::total_cmp);
+    let mid = xs.len() / 2;
+    if xs.len().is_multiple_of(2) {
+        (xs[mid - 1] + xs[mid]) / 2.0
+    } else {
+        xs[mid]
+    }
+}
+
+impl Candles {
+    pub fn detect_outliers(&self, method: OutlierMethod) -> Vec<usize> {
+        let len = self.time.len();
+        let window = match method {
+            OutlierMethod::MedianFilter { window, .. }
+            | OutlierMethod::MaxDeviation { window, .. } => window,
+        };
+        let half = window / 2;
+        let mut neighbours = Vec::with_capacity(window);
+        let mut is_outlier = |xs: &[f64], i: usize| {
+            neighbours.clear();
+            neighbours.extend(
+                (i.saturating_sub(half)..(i + half + 1).min(len))
+                    .filter(|&j| j != i)
+                    .map(|j| xs[j]),
+            );
+            let center = median(&mut neighbours);
+            let limit = match method {
+                OutlierMethod::MedianFilter { threshold, .. } => {
+                    for x in neighbours.iter_mut() {
+                        *x = (*x - center).abs();
+                    }
+                    threshold * median(&mut neighbours)
+                }
+                OutlierMethod::MaxDeviation { fraction, .. } => fraction * center.abs(),
+            };
+            (xs[i] - center).abs() > limit
+        };
+        (0..len)
+            .filter(|&i| {
+                is_outlier(&self.high, i) || is_outlier(&self.low, i) || is_outlier(&self.close, i)
+            })
+            .collect()
+    }
+
+    /// Replaces the prices of flagged bars by linear interpolation between the
+    /// nearest unflagged bars, or copies the nearest one at either end.
+    /// Volume and extra columns are left as they are.
+    pub fn repair_outliers(&self, method: OutlierMethod) -> Candles {
+        let outliers = self.detect_outliers(method);
+        let mut candles = self.clone();
+        let mut flagged = vec![false; self.time.len()];
+        for &i in &outliers {
+            flagged[i] = true;
+        }
+        for &i in &outliers {
+            let before = (0..i).rev().find(|&j| !flagged[j]);
+            let after = (i + 1..self.time.len()).find(|&j| !flagged[j]);
+            let interpolate = |xs: &[f64]| match (before, after) {
+                (Some(a), Some(b)) => {
+                    let w = (i - a) as f64 / (b - a) as f64;
+                    xs[a] + w * (xs[b] - xs[a])
+                }
+                (Some(j), None) | (None, Some(j)) => xs[j],
+                (None, None) => xs[i],
+            };
+            candles.open[i] = interpolate(&self.open);
+            candles.high[i] = interpolate(&self.high);
+            candles.low[i] = interpolate(&self.low);
+            candles.close[i] = interpolate(&self.close);
+        }
+        candles
+    }
+}
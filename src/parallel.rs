@@ -0,0 +1,44 @@
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+#[cfg(feature = "rayon")]
+use crate::{
+    candle::{CandleRef, Candles},
+    slice::CandlesSlice,
+};
+
+/// Below this many items the work is done on the calling thread.
+#[cfg(feature = "rayon")]
+const MIN_PARALLEL_LEN: usize = 1 << 14;
+
+/// Maps `0..len` through `f`, in parallel with the `rayon` feature.
+#[cfg(feature = "rayon")]
+pub(crate) fn map_indices<T: Send>(len: usize, f: impl Fn(usize) -> T + Sync + Send) -> Vec<T> {
+    if len < MIN_PARALLEL_LEN {
+        (0..len).map(f).collect()
+    } else {
+        (0..len).into_par_iter().map(f).collect()
+    }
+}
+
+#[cfg(not(feature = "rayon"))]
+pub(crate) fn map_indices<T>(len: usize, f: impl Fn(usize) -> T) -> Vec<T> {
+    (0..len).map(f).collect()
+}
+
+#[cfg(feature = "rayon")]
+impl Candles {
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = CandleRef<'_>> + '_ {
+        self.as_slice().par_iter()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a> CandlesSlice<'a> {
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = CandleRef<'a>> + 'a {
+        let slice = *self;
+        (0..slice.len())
+            .into_par_iter()
+            .map(move |index| slice.at(index).unwrap())
+    }
+}
@@ -0,0 +1,100 @@
+//! Hive-style partitions, one file per symbol and UTC day under
+//! `base/symbol=<symbol>/date=<YYYY-MM-DD>/`, as data lakes expect.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{NaiveDate, Utc};
+
+use crate::{candle::Candles, error::ErfurtError, resample::CalendarPeriod, slice::CandlesSlice};
+
+/// File format of each partition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PartitionFormat {
+    /// [`Candles::save_bin`].
+    Binary,
+    #[cfg(feature = "zstd")]
+    Compressed(crate::compress::Codec),
+    #[cfg(feature = "arrow")]
+    Parquet,
+    #[cfg(feature = "ndjson")]
+    Ndjson,
+}
+
+impl PartitionFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PartitionFormat::Binary => "bin",
+            #[cfg(feature = "zstd")]
+            PartitionFormat::Compressed(_) => "erfz",
+            #[cfg(feature = "arrow")]
+            PartitionFormat::Parquet => "parquet",
+            #[cfg(feature = "ndjson")]
+            PartitionFormat::Ndjson => "ndjson",
+        }
+    }
+
+    fn write(&self, candles: &Candles, path: &Path) -> Result<(), ErfurtError> {
+        match *self {
+            PartitionFormat::Binary => candles.save_bin(path)?,
+            #[cfg(feature = "zstd")]
+            PartitionFormat::Compressed(codec) => candles.save_compressed(path, codec)?,
+            #[cfg(feature = "arrow")]
+            PartitionFormat::Parquet => candles.to_parquet(path)?,
+            #[cfg(feature = "ndjson")]
+            PartitionFormat::Ndjson => candles
+                .write_ndjson(fs::File::create(path)?)
+                .map_err(crate::ndjson::NdjsonError::from)?,
+        }
+        Ok(())
+    }
+}
+
+/// Percent-encodes every byte but ASCII letters, digits, `-`, `_` and `.`,
+/// so ids such as `BTC/USDT` stay one path segment.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.') {
+            escaped.push(byte as char);
+        } else {
+            escaped.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    escaped
+}
+
+/// Directory of the partition of `symbol` on `date` under `base`.
+pub fn partition_path(base: impl AsRef<Path>, symbol: &str, date: NaiveDate) -> PathBuf {
+    base.as_ref()
+        .join(format!("symbol={}", escape(symbol)))
+        .join(format!("date={}", date.format("%Y-%m-%d")))
+}
+
+impl Candles {
+    /// Bars grouped by UTC day.
+    pub fn partition_by_day(&self) -> Vec<(NaiveDate, CandlesSlice<'_>)> {
+        self.group_by_period(CalendarPeriod::Day, Utc)
+    }
+
+    /// Writes one `data.<extension>` file per UTC day under
+    /// [`partition_path`], creating directories as needed and replacing
+    /// existing files, and returns the files written.
+    pub fn write_partitions(
+        &self,
+        base: impl AsRef<Path>,
+        format: PartitionFormat,
+    ) -> Result<Vec<PathBuf>, ErfurtError> {
+        let mut paths = Vec::new();
+        for (date, slice) in self.partition_by_day() {
+            let dir = partition_path(&base, &self.id, date);
+            fs::create_dir_all(&dir)?;
+            let path = dir.join(format!("data.{}", format.extension()));
+            format.write(&slice.to_candles(), &path)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
@@ -0,0 +1,243 @@
+use chrono::{DateTime, Utc};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::candle::Candles;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Pattern {
+    Doji,
+    Hammer,
+    InvertedHammer,
+    Marubozu,
+    Engulfing,
+    Harami,
+    MorningStar,
+    EveningStar,
+    ThreeWhiteSoldiers,
+    ThreeBlackCrows,
+}
+
+impl Pattern {
+    pub const ALL: [Pattern; 10] = [
+        Pattern::Doji,
+        Pattern::Hammer,
+        Pattern::InvertedHammer,
+        Pattern::Marubozu,
+        Pattern::Engulfing,
+        Pattern::Harami,
+        Pattern::MorningStar,
+        Pattern::EveningStar,
+        Pattern::ThreeWhiteSoldiers,
+        Pattern::ThreeBlackCrows,
+    ];
+
+    /// Number of bars the pattern spans.
+    pub fn bars(&self) -> usize {
+        match self {
+            Pattern::Doji | Pattern::Hammer | Pattern::InvertedHammer | Pattern::Marubozu => 1,
+            Pattern::Engulfing | Pattern::Harami => 2,
+            Pattern::MorningStar
+            | Pattern::EveningStar
+            | Pattern::ThreeWhiteSoldiers
+            | Pattern::ThreeBlackCrows => 3,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Direction {
+    Bullish,
+    Bearish,
+    Neutral,
+}
+
+/// Shape thresholds. Trend context is not considered, so hammers and
+/// inverted hammers are reported as neutral.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PatternConfig {
+    /// Maximum body as a fraction of the range for a doji.
+    pub doji_body: f64,
+    /// Minimum long shadow as a multiple of the body.
+    pub long_shadow: f64,
+    /// Maximum short shadow as a fraction of the range.
+    pub short_shadow: f64,
+    /// Maximum shadows as a fraction of the range for a marubozu.
+    pub marubozu_shadow: f64,
+    /// Maximum star body as a fraction of the first candle body.
+    pub star_body: f64,
+}
+
+impl Default for PatternConfig {
+    fn default() -> Self {
+        PatternConfig {
+            doji_body: 0.1,
+            long_shadow: 2.0,
+            short_shadow: 0.1,
+            marubozu_shadow: 0.05,
+            star_body: 0.3,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PatternMatch {
+    pub pattern: Pattern,
+    pub direction: Direction,
+    /// Index of the last bar of the pattern.
+    pub index: usize,
+    pub time: DateTime<Utc>,
+}
+
+#[derive(Clone, Copy)]
+struct Bar {
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+}
+
+impl Bar {
+    fn body(&self) -> f64 {
+        (self.close - self.open).abs()
+    }
+
+    fn range(&self) -> f64 {
+        self.high - self.low
+    }
+
+    fn upper_shadow(&self) -> f64 {
+        self.high - self.open.max(self.close)
+    }
+
+    fn lower_shadow(&self) -> f64 {
+        self.open.min(self.close) - self.low
+    }
+
+    fn bullish(&self) -> bool {
+        self.close > self.open
+    }
+
+    fn bearish(&self) -> bool {
+        self.close < self.open
+    }
+
+    fn body_top(&self) -> f64 {
+        self.open.max(self.close)
+    }
+
+    fn body_bottom(&self) -> f64 {
+        self.open.min(self.close)
+    }
+
+    fn color(&self) -> Direction {
+        if self.bullish() {
+            Direction::Bullish
+        } else if self.bearish() {
+            Direction::Bearish
+        } else {
+            Direction::Neutral
+        }
+    }
+}
+
+fn matches(pattern: Pattern, bars: &[Bar], config: &PatternConfig) -> Option<Direction> {
+    match (pattern, bars) {
+        (Pattern::Doji, [a]) => (a.range() > 0.0 && a.body() <= config.doji_body * a.range())
+            .then_some(Direction::Neutral),
+        (Pattern::Hammer, [a]) => (a.range() > 0.0
+            && a.lower_shadow() >= config.long_shadow * a.body()
+            && a.upper_shadow() <= config.short_shadow * a.range())
+        .then_some(Direction::Neutral),
+        (Pattern::InvertedHammer, [a]) => (a.range() > 0.0
+            && a.upper_shadow() >= config.long_shadow * a.body()
+            && a.lower_shadow() <= config.short_shadow * a.range())
+        .then_some(Direction::Neutral),
+        (Pattern::Marubozu, [a]) => (a.body() > 0.0
+            && a.upper_shadow() <= config.marubozu_shadow * a.range()
+            && a.lower_shadow() <= config.marubozu_shadow * a.range())
+        .then(|| a.color()),
+        (Pattern::Engulfing, [a, b]) => {
+            let opposite = (a.bearish() && b.bullish()) || (a.bullish() && b.bearish());
+            (opposite
+                && b.body_top() >= a.body_top()
+                && b.body_bottom() <= a.body_bottom()
+                && b.body() > a.body())
+            .then(|| b.color())
+        }
+        (Pattern::Harami, [a, b]) => {
+            let opposite = (a.bearish() && b.bullish()) || (a.bullish() && b.bearish());
+            (opposite
+                && b.body_top() <= a.body_top()
+                && b.body_bottom() >= a.body_bottom()
+                && b.body() < a.body())
+            .then(|| b.color())
+        }
+        (Pattern::MorningStar, [a, b, c]) => (a.bearish()
+            && b.body() <= config.star_body * a.body()
+            && b.body_top() < a.body_bottom()
+            && c.bullish()
+            && c.close > (a.open + a.close) / 2.0)
+            .then_some(Direction::Bullish),
+        (Pattern::EveningStar, [a, b, c]) => (a.bullish()
+            && b.body() <= config.star_body * a.body()
+            && b.body_bottom() > a.body_top()
+            && c.bearish()
+            && c.close < (a.open + a.close) / 2.0)
+            .then_some(Direction::Bearish),
+        (Pattern::ThreeWhiteSoldiers, [a, b, c]) => (a.bullish()
+            && b.bullish()
+            && c.bullish()
+            && b.close > a.close
+            && c.close > b.close
+            && b.open > a.open
+            && b.open < a.close
+            && c.open > b.open
+            && c.open < b.close)
+            .then_some(Direction::Bullish),
+        (Pattern::ThreeBlackCrows, [a, b, c]) => (a.bearish()
+            && b.bearish()
+            && c.bearish()
+            && b.close < a.close
+            && c.close < b.close
+            && b.open < a.open
+            && b.open > a.close
+            && c.open < b.open
+            && c.open > b.close)
+            .then_some(Direction::Bearish),
+        _ => None,
+    }
+}
+
+impl Candles {
+    pub fn detect(&self, pattern: Pattern) -> Vec<PatternMatch> {
+        self.detect_with(pattern, &PatternConfig::default())
+    }
+
+    pub fn detect_with(&self, pattern: Pattern, config: &PatternConfig) -> Vec<PatternMatch> {
+        let bars = (0..self.time.len())
+            .map(|i| Bar {
+                open: self.open[i],
+                high: self.high[i],
+                low: self.low[i],
+                close: self.close[i],
+            })
+            .collect::<Vec<_>>();
+        let n = pattern.bars();
+        bars.windows(n)
+            .enumerate()
+            .filter_map(|(start, window)| {
+                matches(pattern, window, config).map(|direction| PatternMatch {
+                    pattern,
+                    direction,
+                    index: start + n - 1,
+                    time: self.time[start + n - 1],
+                })
+            })
+            .collect()
+    }
+}
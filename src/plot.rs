@@ -0,0 +1,166 @@
+//! Candlestick charts through `plotters`, with a volume pane under the price
+//! pane when the series has volume. The file extension picks the backend:
+//! `.svg` writes SVG, anything else a bitmap format such as PNG.
+
+use std::{fmt, path::Path};
+
+use chrono::Duration;
+use plotters::{coord::Shift, prelude::*};
+
+use crate::{candle::Candles, kernels};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlotOptions {
+    pub width: u32,
+    pub height: u32,
+    pub title: Option<String>,
+    /// Share of the height given to the volume pane; zero hides it.
+    pub volume_pane: f64,
+}
+
+impl Default for PlotOptions {
+    fn default() -> Self {
+        PlotOptions {
+            width: 1280,
+            height: 720,
+            title: None,
+            volume_pane: 0.25,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PlotError {
+    Empty,
+    Drawing(String),
+}
+
+impl fmt::Display for PlotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlotError::Empty => write!(f, "nothing to plot"),
+            PlotError::Drawing(msg) => write!(f, "drawing failed: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for PlotError {}
+
+fn drawing<E: std::error::Error + Send + Sync>(err: DrawingAreaErrorKind<E>) -> PlotError {
+    PlotError::Drawing(err.to_string())
+}
+
+impl Candles {
+    pub fn plot_candlestick<P: AsRef<Path>>(
+        &self,
+        path: P,
+        options: PlotOptions,
+    ) -> Result<(), PlotError> {
+        let path = path.as_ref();
+        let size = (options.width, options.height);
+        if path.extension().is_some_and(|ext| ext == "svg") {
+            self.draw_candlestick(SVGBackend::new(path, size).into_drawing_area(), &options)
+        } else {
+            self.draw_candlestick(BitMapBackend::new(path, size).into_drawing_area(), &options)
+        }
+    }
+
+    /// Draws onto any `plotters` drawing area, for backends the file-based
+    /// entry point does not cover.
+    pub fn draw_candlestick<DB: DrawingBackend>(
+        &self,
+        root: DrawingArea<DB, Shift>,
+        options: &PlotOptions,
+    ) -> Result<(), PlotError> {
+        let len = self.time.len();
+        if len == 0 {
+            return Err(PlotError::Empty);
+        }
+        let first = self.time[0];
+        let last = self.time[len - 1];
+        // Average spacing, so each bar gets the same slot width.
+        let step = if len > 1 {
+            (last - first) / (len as i32 - 1)
+        } else {
+            Duration::minutes(1)
+        };
+        let x_range = (first - step)..(last + step);
+        root.fill(&WHITE).map_err(drawing)?;
+        let root = match &options.title {
+            Some(title) => root.titled(title, ("sans-serif", 20)).map_err(drawing)?,
+            None => root,
+        };
+        let volume = self.volume.as_ref().filter(|_| options.volume_pane > 0.0);
+        let (price_area, volume_area) = match volume {
+            Some(_) => {
+                let height = root.dim_in_pixel().1 as f64;
+                let split = (height * (1.0 - options.volume_pane.min(1.0))) as u32;
+                let (upper, lower) = root.split_vertically(split);
+                (upper, Some(lower))
+            }
+            None => (root, None),
+        };
+        let body_width =
+            ((price_area.dim_in_pixel().0 as f64 / (len + 2) as f64) * 0.7).max(1.0) as u32;
+
+        let low = kernels::min(&self.low);
+        let high = kernels::max(&self.high);
+        let pad = ((high - low) * 0.05).max(f64::EPSILON);
+        let mut chart = ChartBuilder::on(&price_area)
+            .margin(10)
+            .x_label_area_size(if volume_area.is_some() { 0 } else { 30 })
+            .y_label_area_size(60)
+            .build_cartesian_2d(x_range.clone(), (low - pad)..(high + pad))
+            .map_err(drawing)?;
+        chart
+            .configure_mesh()
+            .light_line_style(WHITE)
+            .draw()
+            .map_err(drawing)?;
+        chart
+            .draw_series((0..len).map(|i| {
+                CandleStick::new(
+                    self.time[i],
+                    self.open[i],
+                    self.high[i],
+                    self.low[i],
+                    self.close[i],
+                    GREEN.filled(),
+                    RED.filled(),
+                    body_width,
+                )
+            }))
+            .map_err(drawing)?;
+
+        if let (Some(area), Some(volume)) = (volume_area, volume) {
+            let max = kernels::max(volume).max(f64::EPSILON);
+            let mut chart = ChartBuilder::on(&area)
+                .margin(10)
+                .x_label_area_size(30)
+                .y_label_area_size(60)
+                .build_cartesian_2d(x_range, 0.0..max * 1.05)
+                .map_err(drawing)?;
+            chart
+                .configure_mesh()
+                .light_line_style(WHITE)
+                .y_labels(3)
+                .draw()
+                .map_err(drawing)?;
+            let half = step * 7 / 20;
+            chart
+                .draw_series((0..len).map(|i| {
+                    let color = if self.close[i] >= self.open[i] {
+                        GREEN
+                    } else {
+                        RED
+                    };
+                    Rectangle::new(
+                        [(self.time[i] - half, 0.0), (self.time[i] + half, volume[i])],
+                        color.mix(0.6).filled(),
+                    )
+                }))
+                .map_err(drawing)?;
+        }
+        price_area.present().map_err(drawing)
+    }
+}
@@ -0,0 +1,230 @@
+use chrono::{DateTime, Utc};
+
+use crate::{candle::Candles, symbol::Symbol};
+
+/// Prices that drive a point and figure chart.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PnfSource {
+    #[default]
+    Close,
+    /// Extends the current column from the high or low first and looks at
+    /// the other extreme for a reversal only when it did not extend.
+    HighLow,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PnfKind {
+    /// Rising column.
+    X,
+    /// Falling column.
+    O,
+}
+
+/// Column of boxes between the box lines `low` and `high`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PnfColumn {
+    pub kind: PnfKind,
+    pub low: f64,
+    pub high: f64,
+    pub boxes: usize,
+    /// Bar that started the column.
+    pub start: DateTime<Utc>,
+    /// Last bar that extended it.
+    pub end: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PnfChart {
+    pub id: Symbol,
+    pub box_size: f64,
+    pub reversal: usize,
+    pub columns: Vec<PnfColumn>,
+}
+
+impl PnfChart {
+    /// One pseudo-candle per column stamped with its start, opening at the
+    /// column's first box and closing at its last, for plotting.
+    pub fn to_candles(&self) -> Candles {
+        let mut candles = Candles::with_capacity(&self.id, self.columns.len(), false);
+        for column in &self.columns {
+            let (open, close) = match column.kind {
+                PnfKind::X => (column.low, column.high),
+                PnfKind::O => (column.high, column.low),
+            };
+            candles.push_row(open, column.high, column.low, close, None, column.start);
+        }
+        candles
+    }
+}
+
+/// Column under construction, in whole boxes.
+#[derive(Clone, Copy)]
+struct Open {
+    kind: PnfKind,
+    low: i64,
+    high: i64,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+}
+
+struct Builder {
+    box_size: f64,
+    reversal: i64,
+    reference: Option<(i64, DateTime<Utc>)>,
+    current: Option<Open>,
+    columns: Vec<PnfColumn>,
+}
+
+impl Builder {
+    /// Box lines at or below and at or above `price`, snapping values a
+    /// rounding error away from a line onto it.
+    fn lines(&self, price: f64) -> (i64, i64) {
+        let boxes = price / self.box_size;
+        let nearest = boxes.round();
+        if (boxes - nearest).abs() < 1e-9 {
+            (nearest as i64, nearest as i64)
+        } else {
+            (boxes.floor() as i64, boxes.ceil() as i64)
+        }
+    }
+
+    fn close(&mut self, column: Open) {
+        self.columns.push(PnfColumn {
+            kind: column.kind,
+            low: column.low as f64 * self.box_size,
+            high: column.high as f64 * self.box_size,
+            boxes: (column.high - column.low) as usize,
+            start: column.start,
+            end: column.end,
+        });
+    }
+
+    /// Extends the current column towards `price`; true when it moved.
+    fn extend(&mut self, price: f64, time: DateTime<Utc>) -> bool {
+        let (below, above) = self.lines(price);
+        let Some(column) = self.current.as_mut() else {
+            return false;
+        };
+        match column.kind {
+            PnfKind::X if below > column.high => column.high = below,
+            PnfKind::O if above < column.low => column.low = above,
+            _ => return false,
+        }
+        column.end = time;
+        true
+    }
+
+    /// Starts a new column when `price` moves `reversal` boxes against the
+    /// current one, or one box away from the first price.
+    fn reverse(&mut self, price: f64, time: DateTime<Utc>) {
+        let (below, above) = self.lines(price);
+        let next = match (self.current, self.reference) {
+            (Some(column), _) => match column.kind {
+                PnfKind::X if above <= column.high - self.reversal => Some(Open {
+                    kind: PnfKind::O,
+                    low: above,
+                    high: column.high,
+                    start: time,
+                    end: time,
+                }),
+                PnfKind::O if below >= column.low + self.reversal => Some(Open {
+                    kind: PnfKind::X,
+                    low: column.low,
+                    high: below,
+                    start: time,
+                    end: time,
+                }),
+                _ => None,
+            },
+            (None, Some((line, start))) if below > line => Some(Open {
+                kind: PnfKind::X,
+                low: line,
+                high: below,
+                start,
+                end: time,
+            }),
+            (None, Some((line, start))) if above < line => Some(Open {
+                kind: PnfKind::O,
+                low: above,
+                high: line,
+                start,
+                end: time,
+            }),
+            (None, Some(_)) => None,
+            (None, None) => {
+                self.reference = Some((self.lines(price).0, time));
+                None
+            }
+        };
+        if let Some(next) = next {
+            if let Some(previous) = self.current.replace(next) {
+                self.close(previous);
+            }
+        }
+    }
+
+    fn update(&mut self, price: f64, time: DateTime<Utc>) {
+        if !self.extend(price, time) {
+            self.reverse(price, time);
+        }
+    }
+}
+
+impl Candles {
+    /// Point and figure chart of the closes with `box_size` boxes, reversing
+    /// after `reversal` boxes against the current column. `None` unless
+    /// `box_size` is positive and `reversal` at least one.
+    pub fn to_point_and_figure(&self, box_size: f64, reversal: usize) -> Option<PnfChart> {
+        self.to_point_and_figure_with(box_size, reversal, PnfSource::Close)
+    }
+
+    pub fn to_point_and_figure_with(
+        &self,
+        box_size: f64,
+        reversal: usize,
+        source: PnfSource,
+    ) -> Option<PnfChart> {
+        if box_size.is_nan() || box_size <= 0.0 || reversal == 0 {
+            return None;
+        }
+        let mut builder = Builder {
+            box_size,
+            reversal: reversal as i64,
+            reference: None,
+            current: None,
+            columns: Vec::new(),
+        };
+        for i in 0..self.time.len() {
+            let time = self.time[i];
+            match source {
+                PnfSource::Close => builder.update(self.close[i], time),
+                PnfSource::HighLow => match builder.current.map(|column| column.kind) {
+                    Some(kind) => {
+                        let (first, second) = match kind {
+                            PnfKind::X => (self.high[i], self.low[i]),
+                            PnfKind::O => (self.low[i], self.high[i]),
+                        };
+                        if !builder.extend(first, time) {
+                            builder.reverse(second, time);
+                        }
+                    }
+                    None => {
+                        builder.update(self.high[i], time);
+                        if builder.current.is_none() {
+                            builder.update(self.low[i], time);
+                        }
+                    }
+                },
+            }
+        }
+        if let Some(column) = builder.current.take() {
+            builder.close(column);
+        }
+        Some(PnfChart {
+            id: self.id.clone(),
+            box_size,
+            reversal,
+            columns: builder.columns,
+        })
+    }
+}
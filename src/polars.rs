@@ -0,0 +1,99 @@
+use chrono::DateTime;
+use polars::prelude::*;
+
+use crate::{
+    candle::{Candles, VolumePolicy},
+    extra::ExtraColumns,
+    symbol::Symbol,
+};
+
+const COLUMNS: [&str; 6] = ["time", "open", "high", "low", "close", "volume"];
+
+fn float_column(df: &DataFrame, name: &str) -> PolarsResult<Vec<f64>> {
+    let column = df.column(name)?.cast(&DataType::Float64)?;
+    Ok(column
+        .f64()?
+        .iter()
+        .map(|value| value.unwrap_or(f64::NAN))
+        .collect())
+}
+
+impl Candles {
+    pub fn from_dataframe(id: impl Into<Symbol>, df: &DataFrame) -> PolarsResult<Candles> {
+        let time = df.column("time")?.cast(&DataType::Datetime(
+            TimeUnit::Microseconds,
+            Some(TimeZone::UTC),
+        ))?;
+        let time = time
+            .datetime()?
+            .physical()
+            .iter()
+            .map(|value| {
+                value
+                    .and_then(DateTime::from_timestamp_micros)
+                    .ok_or_else(|| polars_err!(ComputeError: "invalid or null time value"))
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let volume = if df.schema().contains("volume") {
+            Some(float_column(df, "volume")?)
+        } else {
+            None
+        };
+        let mut extra = ExtraColumns::new();
+        for (name, dtype) in df.schema().iter() {
+            if !COLUMNS.contains(&name.as_str()) && dtype.is_primitive_numeric() {
+                extra.insert(name.as_str(), float_column(df, name)?);
+            }
+        }
+        Ok(Candles {
+            id: id.into(),
+            open: float_column(df, "open")?,
+            high: float_column(df, "high")?,
+            low: float_column(df, "low")?,
+            close: float_column(df, "close")?,
+            volume,
+            time,
+            extra,
+            labels: None,
+            volume_policy: VolumePolicy::Optional,
+            instrument: None,
+        })
+    }
+}
+
+impl TryFrom<&Candles> for DataFrame {
+    type Error = PolarsError;
+
+    fn try_from(candles: &Candles) -> Result<Self, Self::Error> {
+        let time = candles
+            .time
+            .iter()
+            .map(|time| time.timestamp_micros())
+            .collect::<Vec<_>>();
+        let mut columns = vec![
+            Column::new("time".into(), time).cast(&DataType::Datetime(
+                TimeUnit::Microseconds,
+                Some(TimeZone::UTC),
+            ))?,
+            Column::new("open".into(), &candles.open),
+            Column::new("high".into(), &candles.high),
+            Column::new("low".into(), &candles.low),
+            Column::new("close".into(), &candles.close),
+        ];
+        if let Some(volume) = &candles.volume {
+            columns.push(Column::new("volume".into(), volume));
+        }
+        for (name, xs) in candles.extra.iter() {
+            columns.push(Column::new(name.into(), xs));
+        }
+        DataFrame::new(candles.time.len(), columns)
+    }
+}
+
+impl TryFrom<&DataFrame> for Candles {
+    type Error = PolarsError;
+
+    fn try_from(df: &DataFrame) -> Result<Self, Self::Error> {
+        Candles::from_dataframe(Symbol::default(), df)
+    }
+}
@@ -1,4 +1,71 @@
+pub use crate::adjust::CorporateAction;
+pub use crate::bars::BarSpec;
+pub use crate::bars::OrderFlow;
+pub use crate::bars::Side;
+pub use crate::bars::TimeBarBuilder;
+pub use crate::bars::Trade;
+pub use crate::bounded::BoundedCandles;
+pub use crate::builder::CandlesBuilder;
+pub use crate::cache::CandleCache;
 pub use crate::candle::Candle;
 pub use crate::candle::CandleExt;
+pub use crate::candle::CandleRef;
 pub use crate::candle::Candles;
 pub use crate::candle::CandlesExt;
+pub use crate::candle::PushError;
+pub use crate::candle::Upsert;
+pub use crate::candle::VolumePolicy;
+pub use crate::causal::CausalCandles;
+pub use crate::combine::CombineOp;
+pub use crate::cursor::CandlesCursor;
+pub use crate::delta::CandlesDelta;
+pub use crate::diff::CandleDiff;
+pub use crate::drawdown::Drawdown;
+pub use crate::edit::CandleMut;
+pub use crate::edit::SetError;
+pub use crate::error::ErfurtError;
+pub use crate::extra::ExtraColumns;
+pub use crate::field::Field;
+pub use crate::gaps::GapFill;
+pub use crate::index::CandlesIndex;
+pub use crate::indicators::IncrementalIndicator;
+pub use crate::indicators::Indicator;
+pub use crate::instrument::InstrumentSpec;
+pub use crate::interpolate::Interpolation;
+pub use crate::kagi::KagiChart;
+pub use crate::line_break::LineBreakChart;
+pub use crate::map::Align;
+pub use crate::map::CandlesMap;
+pub use crate::merge::MergeError;
+pub use crate::merge::MergePolicy;
+pub use crate::normalize::Normalization;
+pub use crate::outliers::OutlierMethod;
+pub use crate::partition::PartitionFormat;
+pub use crate::patterns::Pattern;
+pub use crate::patterns::PatternMatch;
+pub use crate::pnf::PnfChart;
+pub use crate::profile::PriceBins;
+pub use crate::profile::VolumeProfile;
+pub use crate::quality::QualityReport;
+pub use crate::reconcile::ReconcilePolicy;
+pub use crate::reindex::FillPolicy;
+pub use crate::replay::ReplayError;
+pub use crate::replay::ReplaySpeed;
+pub use crate::resample::CalendarPeriod;
+pub use crate::resample::LabelReducer;
+pub use crate::sample::Sampling;
+pub use crate::session::SessionSpec;
+pub use crate::session::TradingSession;
+pub use crate::session::Weekdays;
+pub use crate::slice::CandlesSlice;
+pub use crate::snapshot::CandlesSnapshot;
+pub use crate::sort::Keep;
+pub use crate::source::CandleSource;
+pub use crate::stats::CandlesStats;
+pub use crate::symbol::Symbol;
+pub use crate::timeframe::Timeframe;
+pub use crate::timestamp::Timestamp;
+pub use crate::validation::ValidationError;
+pub use crate::volatility::VolEstimator;
+pub use crate::window::CandleWindow;
+pub use crate::zoned::ZonedCandles;
@@ -0,0 +1,54 @@
+use crate::candle::Candles;
+
+impl Candles {
+    /// Applies `f` to the open, high, low and close, leaving time, volume,
+    /// extra columns and labels as they are. High and low are reordered
+    /// afterwards, so a decreasing `f` still yields `low <= high`.
+    pub fn map_prices(&self, f: impl Fn(f64) -> f64) -> Candles {
+        self.map_prices_at(|_, price| f(price))
+    }
+
+    /// Multiplies prices by `factor`, e.g. `0.01` for cents to dollars or a
+    /// contract multiplier.
+    pub fn scale(&self, factor: f64) -> Candles {
+        self.map_prices(|price| price * factor)
+    }
+
+    pub fn offset(&self, delta: f64) -> Candles {
+        self.map_prices(|price| price + delta)
+    }
+
+    /// Multiplies the prices of each bar by the close of the last bar of
+    /// `rates` opening at or before it, NaN before the first rate. Both
+    /// series must be sorted; `rates` quotes the target currency per unit of
+    /// this series' currency.
+    pub fn convert_currency(&self, rates: &Candles) -> Candles {
+        let mut j = 0;
+        let rate = self
+            .time
+            .iter()
+            .map(|&time| {
+                while j < rates.time.len() && rates.time[j] <= time {
+                    j += 1;
+                }
+                j.checked_sub(1).map_or(f64::NAN, |j| rates.close[j])
+            })
+            .collect::<Vec<_>>();
+        self.map_prices_at(|i, price| price * rate[i])
+    }
+
+    fn map_prices_at(&self, f: impl Fn(usize, f64) -> f64) -> Candles {
+        let mut candles = self.clone();
+        for i in 0..self.time.len() {
+            let (mut high, mut low) = (f(i, self.high[i]), f(i, self.low[i]));
+            if high < low {
+                std::mem::swap(&mut high, &mut low);
+            }
+            candles.open[i] = f(i, self.open[i]);
+            candles.high[i] = high;
+            candles.low[i] = low;
+            candles.close[i] = f(i, self.close[i]);
+        }
+        candles
+    }
+}
@@ -0,0 +1,132 @@
+use std::ops::Range;
+
+use crate::{candle::Candles, kernels};
+
+/// Price buckets of a [`VolumeProfile`], spanning the lowest low to the
+/// highest high.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PriceBins {
+    /// This many buckets of equal width.
+    Count(usize),
+    /// Buckets of this width, such as the tick size.
+    Width(f64),
+}
+
+/// Volume traded at each price bucket. Bucket `i` covers
+/// `low + i * width .. low + (i + 1) * width`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VolumeProfile {
+    pub low: f64,
+    pub width: f64,
+    pub volume: Vec<f64>,
+}
+
+impl VolumeProfile {
+    pub fn len(&self) -> usize {
+        self.volume.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.volume.is_empty()
+    }
+
+    pub fn total(&self) -> f64 {
+        kernels::sum(&self.volume)
+    }
+
+    /// Price range of bucket `bin`.
+    pub fn bounds(&self, bin: usize) -> Range<f64> {
+        let start = self.low + bin as f64 * self.width;
+        start..start + self.width
+    }
+
+    /// Bucket with the most volume, the first on ties.
+    pub fn point_of_control(&self) -> Option<usize> {
+        self.volume
+            .iter()
+            .enumerate()
+            .fold(None, |best: Option<(usize, f64)>, (i, &v)| match best {
+                Some((_, most)) if most >= v => best,
+                _ => Some((i, v)),
+            })
+            .map(|(i, _)| i)
+    }
+
+    /// Buckets around the point of control holding at least `fraction` of
+    /// the volume, grown one bucket at a time towards the heavier neighbour.
+    /// `0.7` gives the conventional value area.
+    pub fn value_area(&self, fraction: f64) -> Option<Range<usize>> {
+        let poc = self.point_of_control()?;
+        let target = self.total() * fraction;
+        let (mut from, mut to) = (poc, poc + 1);
+        let mut covered = self.volume[poc];
+        while covered < target && (from > 0 || to < self.len()) {
+            let below = if from > 0 {
+                self.volume[from - 1]
+            } else {
+                f64::NEG_INFINITY
+            };
+            let above = self.volume.get(to).copied().unwrap_or(f64::NEG_INFINITY);
+            if above >= below {
+                covered += above;
+                to += 1;
+            } else {
+                covered += below;
+                from -= 1;
+            }
+        }
+        Some(from..to)
+    }
+}
+
+impl Candles {
+    /// Spreads each bar's volume evenly over its high-low range; a bar with
+    /// no range puts it all at its close. `None` without a volume column,
+    /// without bars or for a non-positive bucket size.
+    pub fn volume_profile(&self, bins: PriceBins) -> Option<VolumeProfile> {
+        let volume = self.volume.as_deref()?;
+        let low = kernels::min(&self.low);
+        let high = kernels::max(&self.high);
+        if !low.is_finite() || !high.is_finite() {
+            return None;
+        }
+        let span = high - low;
+        let (width, len) = match bins {
+            PriceBins::Count(0) => return None,
+            PriceBins::Count(n) if span == 0.0 => (1.0, n),
+            PriceBins::Count(n) => (span / n as f64, n),
+            PriceBins::Width(width) if width > 0.0 => {
+                (width, ((span / width).floor() as usize + 1).max(1))
+            }
+            PriceBins::Width(_) => return None,
+        };
+        let bin_of = |price: f64| (((price - low) / width) as usize).min(len - 1);
+        let mut profile = vec![0.0; len];
+        for i in 0..self.time.len() {
+            let (bar_low, bar_high, v) = (self.low[i], self.high[i], volume[i]);
+            if v.is_nan() || bar_low.is_nan() || bar_high.is_nan() {
+                continue;
+            }
+            if bar_high <= bar_low {
+                profile[bin_of(self.close[i])] += v;
+                continue;
+            }
+            let density = v / (bar_high - bar_low);
+            for (bin, slot) in profile
+                .iter_mut()
+                .enumerate()
+                .take(bin_of(bar_high) + 1)
+                .skip(bin_of(bar_low))
+            {
+                let start = low + bin as f64 * width;
+                let overlap = bar_high.min(start + width) - bar_low.max(start);
+                *slot += density * overlap.max(0.0);
+            }
+        }
+        Some(VolumeProfile {
+            low,
+            width,
+            volume: profile,
+        })
+    }
+}
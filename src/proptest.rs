@@ -0,0 +1,66 @@
+use chrono::{DateTime, Duration, Utc};
+use proptest::{collection::SizeRange, prelude::*};
+
+use crate::{
+    candle::{Candle, Candles},
+    symbol::Symbol,
+};
+
+/// 2100-01-01 in seconds.
+const MAX_SECONDS: i64 = 4_102_444_800;
+
+fn time() -> impl Strategy<Value = DateTime<Utc>> {
+    (0..MAX_SECONDS).prop_map(|seconds| DateTime::from_timestamp(seconds, 0).unwrap())
+}
+
+/// Positive `(open, high, low, close)` with the high and low bounding the
+/// body.
+fn prices() -> impl Strategy<Value = (f64, f64, f64, f64)> {
+    (0.01f64..1e6, -0.5f64..0.5, 0.0f64..0.1, 0.0f64..0.1).prop_map(|(open, change, up, down)| {
+        let close = open * (1.0 + change);
+        let high = open.max(close) * (1.0 + up);
+        let low = open.min(close) * (1.0 - down);
+        (open, high, low, close)
+    })
+}
+
+impl Arbitrary for Candle {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Candle>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (prices(), proptest::option::of(0.0f64..1e9), time())
+            .prop_map(|((open, high, low, close), volume, time)| Candle {
+                open,
+                high,
+                low,
+                close,
+                volume,
+                time,
+                id: Symbol::default(),
+            })
+            .boxed()
+    }
+}
+
+/// Valid series with strictly increasing times; the parameter bounds the
+/// number of bars.
+impl Arbitrary for Candles {
+    type Parameters = SizeRange;
+    type Strategy = BoxedStrategy<Candles>;
+
+    fn arbitrary_with(size: SizeRange) -> Self::Strategy {
+        let rows = proptest::collection::vec((prices(), 0.0f64..1e9, 1i64..86_400), size);
+        (any::<bool>(), time(), rows)
+            .prop_map(|(has_volume, start, rows)| {
+                let mut candles = Candles::with_capacity("", rows.len(), has_volume);
+                let mut time = start;
+                for ((open, high, low, close), volume, step) in rows {
+                    candles.push_row(open, high, low, close, has_volume.then_some(volume), time);
+                    time += Duration::seconds(step);
+                }
+                candles
+            })
+            .boxed()
+    }
+}
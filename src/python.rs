@@ -0,0 +1,221 @@
+//! Python bindings. Build the extension module with
+//! `maturin build --features python,pyo3/extension-module`; maturin builds
+//! the cdylib itself, so the crate type is not set in the manifest.
+
+use chrono::DateTime;
+use numpy::{PyArray1, PyReadonlyArray1};
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyDict};
+
+use crate::{candle::Candles, stats::CandlesStats, validation::ValidationError};
+
+/// `Candles` as the Python class `erfurt.Candles`. Times cross the boundary
+/// as int64 nanoseconds since the epoch, UTC.
+#[pyclass(name = "Candles", module = "erfurt", skip_from_py_object)]
+#[derive(Clone, Debug)]
+pub struct PyCandles(pub Candles);
+
+fn to_vec(array: &PyReadonlyArray1<'_, f64>) -> Vec<f64> {
+    array.as_array().to_vec()
+}
+
+fn invalid(errors: Vec<ValidationError>) -> PyErr {
+    let errors = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+    PyValueError::new_err(errors.join("; "))
+}
+
+#[pymethods]
+impl PyCandles {
+    #[new]
+    #[pyo3(signature = (id, time, open, high, low, close, volume = None))]
+    fn new(
+        id: &str,
+        time: PyReadonlyArray1<'_, i64>,
+        open: PyReadonlyArray1<'_, f64>,
+        high: PyReadonlyArray1<'_, f64>,
+        low: PyReadonlyArray1<'_, f64>,
+        close: PyReadonlyArray1<'_, f64>,
+        volume: Option<PyReadonlyArray1<'_, f64>>,
+    ) -> PyResult<Self> {
+        let candles = Candles {
+            id: id.into(),
+            open: to_vec(&open),
+            high: to_vec(&high),
+            low: to_vec(&low),
+            close: to_vec(&close),
+            volume: volume.as_ref().map(to_vec),
+            time: time
+                .as_array()
+                .iter()
+                .map(|&nanos| DateTime::from_timestamp_nanos(nanos))
+                .collect(),
+            ..Default::default()
+        };
+        candles.validate().map_err(invalid)?;
+        Ok(PyCandles(candles))
+    }
+
+    /// Reads a DataFrame with `time`, `open`, `high`, `low`, `close` and an
+    /// optional `volume` column.
+    #[staticmethod]
+    fn from_pandas(py: Python<'_>, id: &str, df: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let pandas = py.import("pandas")?;
+        let utc = PyDict::new(py);
+        utc.set_item("utc", true)?;
+        let time = pandas
+            .call_method("to_datetime", (df.get_item("time")?,), Some(&utc))?
+            .getattr("dt")?
+            .call_method1("tz_localize", (py.None(),))?
+            .call_method1("to_numpy", ("datetime64[ns]",))?
+            .call_method1("view", ("int64",))?;
+        let column = |name: &str| -> PyResult<Bound<'_, PyAny>> {
+            df.get_item(name)?.call_method1("to_numpy", ("float64",))
+        };
+        let has_volume = df.getattr("columns")?.contains("volume")?;
+        PyCandles::new(
+            id,
+            time.extract()?,
+            column("open")?.extract()?,
+            column("high")?.extract()?,
+            column("low")?.extract()?,
+            column("close")?.extract()?,
+            if has_volume {
+                Some(column("volume")?.extract()?)
+            } else {
+                None
+            },
+        )
+    }
+
+    fn to_pandas<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let pandas = py.import("pandas")?;
+        let utc = PyDict::new(py);
+        utc.set_item("utc", true)?;
+        let columns = PyDict::new(py);
+        columns.set_item(
+            "time",
+            pandas.call_method("to_datetime", (self.time(py)?,), Some(&utc))?,
+        )?;
+        columns.set_item("open", self.open(py))?;
+        columns.set_item("high", self.high(py))?;
+        columns.set_item("low", self.low(py))?;
+        columns.set_item("close", self.close(py))?;
+        if let Some(volume) = self.volume(py) {
+            columns.set_item("volume", volume)?;
+        }
+        for (name, xs) in self.0.extra.iter() {
+            columns.set_item(name, PyArray1::from_slice(py, xs))?;
+        }
+        pandas.call_method1("DataFrame", (columns,))
+    }
+
+    #[getter]
+    fn id(&self) -> &str {
+        &self.0.id
+    }
+
+    #[getter]
+    /// Nanoseconds since the Unix epoch; raises `ValueError` for times outside
+    /// that range rather than returning NaT.
+    fn time<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyArray1<i64>>> {
+        let nanos = self
+            .0
+            .time
+            .iter()
+            .map(|time| {
+                time.timestamp_nanos_opt().ok_or_else(|| {
+                    PyValueError::new_err(format!("time {time} is out of the nanosecond range"))
+                })
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(PyArray1::from_vec(py, nanos))
+    }
+
+    #[getter]
+    fn open<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        PyArray1::from_slice(py, &self.0.open)
+    }
+
+    #[getter]
+    fn high<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        PyArray1::from_slice(py, &self.0.high)
+    }
+
+    #[getter]
+    fn low<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        PyArray1::from_slice(py, &self.0.low)
+    }
+
+    #[getter]
+    fn close<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        PyArray1::from_slice(py, &self.0.close)
+    }
+
+    #[getter]
+    fn volume<'py>(&self, py: Python<'py>) -> Option<Bound<'py, PyArray1<f64>>> {
+        self.0
+            .volume
+            .as_deref()
+            .map(|xs| PyArray1::from_slice(py, xs))
+    }
+
+    fn extra<'py>(&self, py: Python<'py>, name: &str) -> Option<Bound<'py, PyArray1<f64>>> {
+        self.0
+            .extra
+            .get(name)
+            .map(|xs| PyArray1::from_slice(py, xs))
+    }
+
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let Some(stats) = self.0.stats() else {
+            return Ok(None);
+        };
+        let CandlesStats {
+            count,
+            min_close,
+            max_close,
+            mean_close,
+            total_volume,
+            volatility,
+            max_drawdown,
+            ..
+        } = stats;
+        let dict = PyDict::new(py);
+        dict.set_item("count", count)?;
+        dict.set_item("min_close", min_close)?;
+        dict.set_item("max_close", max_close)?;
+        dict.set_item("mean_close", mean_close)?;
+        dict.set_item("total_volume", total_volume)?;
+        dict.set_item("volatility", volatility)?;
+        dict.set_item("max_drawdown", max_drawdown)?;
+        Ok(Some(dict))
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.time.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Candles(id={:?}, len={})",
+            self.0.id.as_str(),
+            self.0.time.len()
+        )
+    }
+}
+
+impl From<Candles> for PyCandles {
+    fn from(candles: Candles) -> Self {
+        PyCandles(candles)
+    }
+}
+
+impl From<PyCandles> for Candles {
+    fn from(candles: PyCandles) -> Self {
+        candles.0
+    }
+}
+
+#[pymodule]
+fn erfurt(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyCandles>()
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    candle::Candles, outliers::OutlierMethod, timeframe::Timeframe, validation::ValidationError,
+};
+
+/// Outlier detection used by [`Candles::quality_report`].
+pub const DEFAULT_OUTLIERS: OutlierMethod = OutlierMethod::MedianFilter {
+    window: 21,
+    threshold: 10.0,
+};
+
+/// Summary of the problems found in a series, for gating ingestion.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QualityReport {
+    pub bars: usize,
+    pub first: Option<DateTime<Utc>>,
+    pub last: Option<DateTime<Utc>>,
+    /// Bar spacing the gaps are measured against.
+    pub timeframe: Option<Timeframe>,
+    /// Columns whose length differs from the time column.
+    pub column_length_errors: usize,
+    /// Bars with a NaN or infinite price or volume.
+    pub non_finite_bars: usize,
+    /// Bars whose high or low does not enclose the open and close.
+    pub inconsistent_bars: usize,
+    /// Bars stamped the same as the bar before them.
+    pub duplicate_bars: usize,
+    /// Bars stamped before the bar before them.
+    pub unsorted_bars: usize,
+    pub gaps: usize,
+    pub missing_bars: usize,
+    pub longest_gap_bars: usize,
+    pub zero_volume_bars: usize,
+    /// `None` without a volume column.
+    pub zero_volume_ratio: Option<f64>,
+    pub outliers: usize,
+}
+
+impl QualityReport {
+    /// True when nothing but zero-volume bars was found.
+    pub fn is_clean(&self) -> bool {
+        self.column_length_errors == 0
+            && self.non_finite_bars == 0
+            && self.inconsistent_bars == 0
+            && self.duplicate_bars == 0
+            && self.unsorted_bars == 0
+            && self.gaps == 0
+            && self.outliers == 0
+    }
+}
+
+impl Candles {
+    /// The most common spacing between consecutive bars.
+    pub fn infer_timeframe(&self) -> Option<Timeframe> {
+        let mut counts = HashMap::new();
+        for pair in self.time.windows(2) {
+            let step = (pair[1] - pair[0]).num_seconds();
+            if step > 0 {
+                *counts.entry(step).or_insert(0usize) += 1;
+            }
+        }
+        counts
+            .into_iter()
+            .max_by_key(|&(step, count)| (count, std::cmp::Reverse(step)))
+            .map(|(step, _)| Timeframe::seconds(step))
+    }
+
+    /// [`Candles::quality_report_with`] against the inferred timeframe and
+    /// [`DEFAULT_OUTLIERS`].
+    pub fn quality_report(&self) -> QualityReport {
+        self.quality_report_with(self.infer_timeframe(), DEFAULT_OUTLIERS)
+    }
+
+    /// Gap statistics are left at zero without a timeframe.
+    pub fn quality_report_with(
+        &self,
+        timeframe: Option<Timeframe>,
+        outliers: OutlierMethod,
+    ) -> QualityReport {
+        let mut report = QualityReport {
+            bars: self.time.len(),
+            first: self.time.first().copied(),
+            last: self.time.last().copied(),
+            timeframe,
+            ..Default::default()
+        };
+        let errors = self.validate().err().unwrap_or_default();
+        let mut non_finite = errors
+            .iter()
+            .filter_map(|err| match err {
+                ValidationError::NonFinite { index, .. } => Some(*index),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        non_finite.dedup();
+        report.non_finite_bars = non_finite.len();
+        for err in &errors {
+            match err {
+                ValidationError::ColumnLength { .. }
+                | ValidationError::ExtraColumnLength { .. } => report.column_length_errors += 1,
+                ValidationError::HighBelowBody { .. } | ValidationError::LowAboveBody { .. } => {
+                    report.inconsistent_bars += 1
+                }
+                _ => {}
+            }
+        }
+        for pair in self.time.windows(2) {
+            if pair[1] == pair[0] {
+                report.duplicate_bars += 1;
+            } else if pair[1] < pair[0] {
+                report.unsorted_bars += 1;
+            }
+        }
+        if let Some(timeframe) = timeframe.filter(|timeframe| timeframe.as_seconds() > 0) {
+            let step = timeframe.duration();
+            for (start, end) in self.find_gaps(timeframe) {
+                let missing = ((end - start).num_seconds() / step.num_seconds()).max(1) as usize;
+                report.gaps += 1;
+                report.missing_bars += missing;
+                report.longest_gap_bars = report.longest_gap_bars.max(missing);
+            }
+        }
+        if let Some(volume) = &self.volume {
+            report.zero_volume_bars = volume.iter().filter(|&&x| x == 0.0).count();
+            report.zero_volume_ratio =
+                (!volume.is_empty()).then(|| report.zero_volume_bars as f64 / volume.len() as f64);
+        }
+        if report.column_length_errors == 0 {
+            report.outliers = self.detect_outliers(outliers).len();
+        }
+        report
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{candle::Candles, diff::CandleDiff};
+
+/// What [`Candles::reconcile`] does with bars deviating from the reference.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RepairAction {
+    /// Only reports them.
+    Flag,
+    /// Overwrites their prices, and their volume when volume is compared,
+    /// with the reference bar.
+    #[default]
+    Replace,
+    /// Removes them.
+    Drop,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReconcilePolicy {
+    /// Relative error allowed per column, as in [`Candles::diff`].
+    pub tolerance: f64,
+    pub action: RepairAction,
+    /// Compares volume too; feeds rarely agree on it, so off by default.
+    pub compare_volume: bool,
+    /// Inserts bars present only in the reference.
+    pub fill_missing: bool,
+}
+
+impl ReconcilePolicy {
+    pub fn new(tolerance: f64) -> Self {
+        ReconcilePolicy {
+            tolerance,
+            action: RepairAction::default(),
+            compare_volume: false,
+            fill_missing: false,
+        }
+    }
+
+    pub fn action(mut self, action: RepairAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    pub fn compare_volume(mut self, compare_volume: bool) -> Self {
+        self.compare_volume = compare_volume;
+        self
+    }
+
+    pub fn fill_missing(mut self, fill_missing: bool) -> Self {
+        self.fill_missing = fill_missing;
+        self
+    }
+}
+
+impl Default for ReconcilePolicy {
+    fn default() -> Self {
+        ReconcilePolicy::new(1e-3)
+    }
+}
+
+/// Outcome of [`Candles::reconcile`]. Indices in `diffs` address the series
+/// as it was before the repair.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReconcileReport {
+    /// Bars found in both series.
+    pub compared: usize,
+    /// Bars with at least one column beyond the tolerance.
+    pub deviating: usize,
+    /// Bars found only in the reference.
+    pub missing: usize,
+    /// Bars found only in this series, which are kept unchanged.
+    pub unmatched: usize,
+    pub replaced: usize,
+    pub dropped: usize,
+    pub filled: usize,
+    pub diffs: Vec<CandleDiff>,
+}
+
+impl ReconcileReport {
+    pub fn is_clean(&self) -> bool {
+        self.diffs.is_empty()
+    }
+}
+
+impl Candles {
+    /// Compares both sorted series by time against `reference` and repairs
+    /// this one by `policy`. The ids are not checked, since feeds often name
+    /// the same instrument differently. Filled bars take NaN for any column
+    /// the reference lacks.
+    pub fn reconcile(&mut self, reference: &Candles, policy: ReconcilePolicy) -> ReconcileReport {
+        let mut diffs = self.diff(reference, policy.tolerance);
+        if !policy.compare_volume {
+            diffs.retain(|diff| {
+                !matches!(
+                    diff,
+                    CandleDiff::Mismatch {
+                        column: "volume",
+                        ..
+                    }
+                )
+            });
+        }
+        let mut report = ReconcileReport::default();
+        let mut deviating: Vec<(usize, usize)> = Vec::new();
+        let mut missing = Vec::new();
+        for diff in &diffs {
+            match *diff {
+                CandleDiff::Mismatch {
+                    index, other_index, ..
+                } => {
+                    if deviating.last().map(|&(i, _)| i) != Some(index) {
+                        deviating.push((index, other_index));
+                    }
+                }
+                CandleDiff::MissingInSelf { other_index, .. } => missing.push(other_index),
+                CandleDiff::MissingInOther { .. } => report.unmatched += 1,
+            }
+        }
+        report.compared = self.time.len() - report.unmatched;
+        report.deviating = deviating.len();
+        report.missing = missing.len();
+
+        let mut dropped = vec![false; self.time.len()];
+        for &(i, j) in &deviating {
+            match policy.action {
+                RepairAction::Flag => {}
+                RepairAction::Replace => {
+                    self.open[i] = reference.open[j];
+                    self.high[i] = reference.high[j];
+                    self.low[i] = reference.low[j];
+                    self.close[i] = reference.close[j];
+                    if policy.compare_volume {
+                        if let (Some(xs), Some(ys)) = (self.volume.as_mut(), &reference.volume) {
+                            xs[i] = ys[j];
+                        }
+                    }
+                    report.replaced += 1;
+                }
+                RepairAction::Drop => {
+                    dropped[i] = true;
+                    report.dropped += 1;
+                }
+            }
+        }
+
+        if !policy.fill_missing {
+            missing.clear();
+        }
+        report.filled = missing.len();
+        if report.dropped > 0 || report.filled > 0 {
+            let mut repaired = self.empty_like();
+            let mut missing = missing.into_iter().peekable();
+            for (i, dropped) in dropped.into_iter().enumerate() {
+                while let Some(j) = missing.next_if(|&j| reference.time[j] < self.time[i]) {
+                    repaired.push_index(reference, j);
+                }
+                if !dropped {
+                    repaired.push_index(self, i);
+                }
+            }
+            for j in missing {
+                repaired.push_index(reference, j);
+            }
+            *self = repaired;
+        }
+        report.diffs = diffs;
+        report
+    }
+}
@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+
+use crate::candle::Candles;
+
+/// How [`Candles::reindex`] fills a requested time with no bar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillPolicy {
+    /// Flat candle at the close of the latest earlier bar with zero volume;
+    /// NaN before the first bar.
+    ForwardFill,
+    /// Candle with all prices and volume set to NaN.
+    Nan,
+}
+
+impl Candles {
+    /// One bar at each of `times`, in the given order: the bar stamped exactly
+    /// then, or a filled one. Extra columns of filled bars are NaN.
+    pub fn reindex(&self, times: &[DateTime<Utc>], fill: FillPolicy) -> Candles {
+        let mut reindexed = self.empty_like();
+        for &time in times {
+            let after = self.time.partition_point(|x| *x <= time);
+            if after > 0 && self.time[after - 1] == time {
+                reindexed.push_index(self, after - 1);
+                continue;
+            }
+            let (price, volume) = match fill {
+                FillPolicy::ForwardFill if after > 0 => (self.close[after - 1], 0.0),
+                _ => (f64::NAN, f64::NAN),
+            };
+            reindexed.push_row(price, price, price, price, Some(volume), time);
+        }
+        reindexed
+    }
+}
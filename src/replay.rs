@@ -0,0 +1,128 @@
+use std::{
+    fmt,
+    iter::FusedIterator,
+    thread,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+
+use crate::{candle::Candles, slice::CandlesSlice};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReplaySpeed {
+    /// Yields every bar at once.
+    Instant,
+    /// Sleeps so bars arrive as far apart as their timestamps.
+    Realtime,
+    /// Like `Realtime`, this many times faster; the factor must be finite
+    /// and positive.
+    Accelerated(f64),
+}
+
+impl ReplaySpeed {
+    /// How many times faster than realtime bars arrive, `None` if unpaced.
+    fn factor(&self) -> Result<Option<f64>, ReplayError> {
+        match *self {
+            ReplaySpeed::Instant => Ok(None),
+            ReplaySpeed::Realtime => Ok(Some(1.0)),
+            ReplaySpeed::Accelerated(factor) if factor.is_finite() && factor > 0.0 => {
+                Ok(Some(factor))
+            }
+            ReplaySpeed::Accelerated(factor) => Err(ReplayError::InvalidSpeed(factor)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReplayError {
+    /// An `Accelerated` factor that is zero, negative or not finite.
+    InvalidSpeed(f64),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::InvalidSpeed(factor) => {
+                write!(f, "cannot replay {factor} times faster than realtime")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// Growing prefixes of a series, one per bar, each paired with the time of
+/// its newest bar. Paced replays sleep on the calling thread and schedule
+/// against the first bar, so slow consumers do not accumulate drift. A bar
+/// due further out than [`Instant`] can represent is waited for with the
+/// longest sleep that fits, rather than released early.
+#[derive(Clone, Debug)]
+pub struct Replay<'a> {
+    candles: CandlesSlice<'a>,
+    next: usize,
+    factor: Option<f64>,
+    started: Option<Instant>,
+}
+
+impl<'a> Replay<'a> {
+    fn wait(&mut self, index: usize) {
+        let Some(factor) = self.factor else {
+            return;
+        };
+        let started = *self.started.get_or_insert_with(Instant::now);
+        let elapsed = (self.candles.time[index] - self.candles.time[0])
+            .to_std()
+            .unwrap_or_default();
+        let offset =
+            Duration::try_from_secs_f64(elapsed.as_secs_f64() / factor).unwrap_or(Duration::MAX);
+        let remaining = match started.checked_add(offset) {
+            Some(due) => due.checked_duration_since(Instant::now()),
+            None => Some(offset),
+        };
+        if let Some(remaining) = remaining {
+            thread::sleep(remaining);
+        }
+    }
+}
+
+impl<'a> Iterator for Replay<'a> {
+    type Item = (DateTime<Utc>, CandlesSlice<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.next;
+        if index >= self.candles.len() {
+            return None;
+        }
+        self.wait(index);
+        self.next += 1;
+        Some((self.candles.time[index], self.candles.slice(0..index + 1)?))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.candles.len() - self.next;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Replay<'_> {}
+
+impl FusedIterator for Replay<'_> {}
+
+impl<'a> CandlesSlice<'a> {
+    pub fn replay(&self, speed: ReplaySpeed) -> Result<Replay<'a>, ReplayError> {
+        Ok(Replay {
+            candles: *self,
+            next: 0,
+            factor: speed.factor()?,
+            started: None,
+        })
+    }
+}
+
+impl Candles {
+    /// The series as it would have been known bar by bar, without copying.
+    pub fn replay(&self, speed: ReplaySpeed) -> Result<Replay<'_>, ReplayError> {
+        self.as_slice().replay(speed)
+    }
+}
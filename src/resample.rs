@@ -0,0 +1,221 @@
+use std::ops::Range;
+
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc, Weekday};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    candle::{Candle, Candles},
+    error::ErfurtError,
+    extra::{self, ExtraColumns},
+    kernels::{max, min, sum},
+    parallel::map_indices,
+    slice::CandlesSlice,
+    timeframe::Timeframe,
+};
+
+/// Calendar buckets, in UTC unless read through [`crate::zoned::ZonedCandles`].
+/// Weeks start on `anchor`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CalendarPeriod {
+    Day,
+    Week { anchor: Weekday },
+    Month,
+    Quarter,
+    Year,
+}
+
+impl CalendarPeriod {
+    pub const ISO_WEEK: CalendarPeriod = CalendarPeriod::Week {
+        anchor: Weekday::Mon,
+    };
+
+    pub fn start_of(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        self.start_date(time.date_naive())
+            .and_time(Default::default())
+            .and_utc()
+    }
+
+    /// First date of the period holding `date`.
+    pub fn start_date(&self, date: NaiveDate) -> NaiveDate {
+        match *self {
+            CalendarPeriod::Day => date,
+            CalendarPeriod::Week { anchor } => {
+                let offset =
+                    (date.weekday().num_days_from_monday() + 7 - anchor.num_days_from_monday()) % 7;
+                date - Days::new(offset as u64)
+            }
+            CalendarPeriod::Month => first_of(date.year(), date.month()),
+            CalendarPeriod::Quarter => first_of(date.year(), (date.month() - 1) / 3 * 3 + 1),
+            CalendarPeriod::Year => first_of(date.year(), 1),
+        }
+    }
+}
+
+/// How the labels of the bars in a bucket combine into the bucket's label.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LabelReducer {
+    First,
+    /// Like the extra columns.
+    #[default]
+    Last,
+    Max,
+    Min,
+    /// Any flag set on a bar is set on the bucket.
+    BitOr,
+}
+
+impl LabelReducer {
+    pub fn reduce(&self, labels: &[u32]) -> u32 {
+        let mut labels = labels.iter().copied();
+        match self {
+            LabelReducer::First => labels.next(),
+            LabelReducer::Last => labels.last(),
+            LabelReducer::Max => labels.max(),
+            LabelReducer::Min => labels.min(),
+            LabelReducer::BitOr => labels.reduce(|a, b| a | b),
+        }
+        .unwrap_or(0)
+    }
+}
+
+fn first_of(year: i32, month: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, 1).unwrap()
+}
+
+fn first(xs: &[f64]) -> f64 {
+    xs.first().copied().unwrap_or(f64::NAN)
+}
+
+fn last(xs: &[f64]) -> f64 {
+    xs.last().copied().unwrap_or(f64::NAN)
+}
+
+/// Counts, quote volume and side volumes add up; every other extra column,
+/// including the cumulative delta, keeps its last value.
+fn reducer(name: &str) -> fn(&[f64]) -> f64 {
+    match name {
+        extra::TRADES | extra::QUOTE_VOLUME | extra::BUY_VOLUME | extra::SELL_VOLUME => sum,
+        _ => last,
+    }
+}
+
+impl Candles {
+    /// Aggregates each `(time, range)` bucket into one bar stamped `time`.
+    pub(crate) fn aggregate_buckets(
+        &self,
+        buckets: &[(DateTime<Utc>, Range<usize>)],
+        labels: LabelReducer,
+    ) -> Candles {
+        let reduce = |xs: &[f64], f: fn(&[f64]) -> f64| {
+            map_indices(buckets.len(), |i| f(&xs[buckets[i].1.clone()]))
+        };
+        Candles {
+            id: self.id.clone(),
+            open: reduce(&self.open, first),
+            high: reduce(&self.high, max),
+            low: reduce(&self.low, min),
+            close: reduce(&self.close, last),
+            volume: self.volume.as_deref().map(|xs| reduce(xs, sum)),
+            time: buckets.iter().map(|(time, _)| *time).collect(),
+            extra: self
+                .extra
+                .iter()
+                .map(|(name, xs)| (name, reduce(xs, reducer(name))))
+                .collect(),
+            labels: self
+                .labels
+                .as_deref()
+                .map(|xs| map_indices(buckets.len(), |i| labels.reduce(&xs[buckets[i].1.clone()]))),
+            volume_policy: self.volume_policy,
+            instrument: self.instrument,
+        }
+    }
+
+    /// Buckets bars by calendar period, stamping each bar with the period
+    /// start, so weekly and monthly bars line up with charting platforms.
+    pub fn resample_calendar(&self, period: CalendarPeriod) -> Candles {
+        self.resample_calendar_with(period, LabelReducer::default())
+    }
+
+    /// Like [`Candles::resample_calendar`], failing on unsorted or duplicate
+    /// times instead of splitting their buckets.
+    pub fn try_resample_calendar(&self, period: CalendarPeriod) -> Result<Candles, ErfurtError> {
+        self.check_sorted()?;
+        Ok(self.resample_calendar(period))
+    }
+
+    /// Like [`Candles::resample_calendar`], combining labels with `labels`.
+    pub fn resample_calendar_with(&self, period: CalendarPeriod, labels: LabelReducer) -> Candles {
+        self.aggregate_buckets(&self.buckets(|time| period.start_of(time)), labels)
+    }
+
+    /// Like [`Candles::resample_calendar`], reducing each bucket with `f`.
+    pub fn resample_calendar_by(
+        &self,
+        period: CalendarPeriod,
+        f: impl FnMut(CandlesSlice<'_>) -> Candle,
+    ) -> Candles {
+        self.aggregate_buckets_by(&self.buckets(|time| period.start_of(time)), f)
+    }
+
+    /// Buckets bars by `timeframe` boundaries counted from the Unix epoch,
+    /// stamping each bar with its boundary.
+    pub fn resample(&self, timeframe: Timeframe) -> Candles {
+        self.resample_with(timeframe, LabelReducer::default())
+    }
+
+    /// Like [`Candles::resample`], combining labels with `labels`.
+    pub fn resample_with(&self, timeframe: Timeframe, labels: LabelReducer) -> Candles {
+        self.aggregate_buckets(&self.buckets(|time| timeframe.floor(time)), labels)
+    }
+
+    /// Like [`Candles::resample`], reducing each bucket with `f`, e.g. to a
+    /// median close or a volume-weighted open.
+    pub fn resample_by(
+        &self,
+        timeframe: Timeframe,
+        f: impl FnMut(CandlesSlice<'_>) -> Candle,
+    ) -> Candles {
+        self.aggregate_buckets_by(&self.buckets(|time| timeframe.floor(time)), f)
+    }
+
+    /// Runs of consecutive bars sharing a bucket start.
+    fn buckets(
+        &self,
+        start_of: impl Fn(DateTime<Utc>) -> DateTime<Utc>,
+    ) -> Vec<(DateTime<Utc>, Range<usize>)> {
+        let mut buckets: Vec<(DateTime<Utc>, Range<usize>)> = Vec::new();
+        for (i, &time) in self.time.iter().enumerate() {
+            let start = start_of(time);
+            match buckets.last_mut() {
+                Some((bucket, range)) if *bucket == start => range.end = i + 1,
+                _ => buckets.push((start, i..i + 1)),
+            }
+        }
+        buckets
+    }
+
+    /// Reduces each `(time, range)` bucket with `f` into one bar stamped
+    /// `time`, whatever time `f` returns. Volume is kept when this series
+    /// has it; extra columns and labels are dropped.
+    pub(crate) fn aggregate_buckets_by(
+        &self,
+        buckets: &[(DateTime<Utc>, Range<usize>)],
+        mut f: impl FnMut(CandlesSlice<'_>) -> Candle,
+    ) -> Candles {
+        let mut candles = Candles {
+            extra: ExtraColumns::new(),
+            labels: None,
+            ..self.empty_like()
+        };
+        candles.reserve(buckets.len());
+        for (time, range) in buckets {
+            let bar = f(self.view(range.clone()));
+            candles.push_row(bar.open, bar.high, bar.low, bar.close, bar.volume, *time);
+        }
+        candles
+    }
+}
@@ -0,0 +1,41 @@
+//! Serializes `Candles` as an array of row objects, e.g. with
+//! `#[serde(with = "erfurt::rows")]`.
+
+use std::fmt;
+
+use serde::{
+    de::{Error, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::candle::{Candle, Candles};
+
+pub fn serialize<S: Serializer>(candles: &Candles, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(candles.iter())
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Candles, D::Error> {
+    struct RowsVisitor;
+
+    impl<'de> Visitor<'de> for RowsVisitor {
+        type Value = Candles;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a sequence of candles")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Candles, A::Error> {
+            let mut candles = Candles::default();
+            while let Some(candle) = seq.next_element::<Candle>()? {
+                candles.try_push(candle).map_err(A::Error::custom)?;
+            }
+            Ok(candles)
+        }
+    }
+
+    deserializer.deserialize_seq(RowsVisitor)
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Rows(#[serde(with = "self")] pub Candles);
@@ -0,0 +1,90 @@
+//! Irregular auxiliary series, such as funding rates or open interest
+//! snapshots, sampled onto the bar times. An observation holds until the next
+//! one; nothing is known before the first.
+
+use chrono::{DateTime, Utc};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::candle::Candles;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Sampling {
+    /// The latest observation at or before the bar time, so a bar never sees
+    /// values observed while it forms.
+    Last,
+    /// Mean over the bar, from its time to the next bar's, weighted by how
+    /// long each observation held. The last bar spans the inferred timeframe.
+    TimeWeightedMean,
+}
+
+/// Latest value at or before `time`.
+fn last_at(times: &[DateTime<Utc>], values: &[f64], time: DateTime<Utc>) -> f64 {
+    let after = times.partition_point(|x| *x <= time);
+    after.checked_sub(1).map_or(f64::NAN, |i| values[i])
+}
+
+/// Mean of the step function through `times` and `values` over
+/// `start..end`, skipping the span before the first observation.
+fn time_weighted_mean(
+    times: &[DateTime<Utc>],
+    values: &[f64],
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> f64 {
+    let after = times.partition_point(|x| *x <= start);
+    let (mut held, mut from) = (after.checked_sub(1), start);
+    let (mut total, mut weight) = (0.0, 0.0);
+    let changes = (after..times.len()).take_while(|&j| times[j] < end);
+    for (next, until) in changes.map(|j| (Some(j), times[j])).chain([(None, end)]) {
+        if let Some(held) = held {
+            let span = (until - from).num_milliseconds() as f64;
+            total += values[held] * span;
+            weight += span;
+        }
+        held = next.or(held);
+        from = until;
+    }
+    if weight > 0.0 {
+        total / weight
+    } else {
+        last_at(times, values, start)
+    }
+}
+
+impl Candles {
+    /// One value per bar from the series `values` observed at `times`, which
+    /// must be sorted. Bars before the first observation are NaN, and pairs
+    /// beyond the shorter of the two slices are ignored.
+    pub fn sample_series(
+        &self,
+        times: &[DateTime<Utc>],
+        values: &[f64],
+        how: Sampling,
+    ) -> Vec<f64> {
+        let len = times.len().min(values.len());
+        let (times, values) = (&times[..len], &values[..len]);
+        match how {
+            Sampling::Last => self
+                .time
+                .iter()
+                .map(|&time| last_at(times, values, time))
+                .collect(),
+            Sampling::TimeWeightedMean => {
+                let step = self.infer_timeframe().map(|timeframe| timeframe.duration());
+                (0..self.time.len())
+                    .map(|i| {
+                        let start = self.time[i];
+                        let end = self
+                            .time
+                            .get(i + 1)
+                            .copied()
+                            .unwrap_or_else(|| step.map_or(start, |step| start + step));
+                        time_weighted_mean(times, values, start, end)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Versioned serde layout of [`Candles`]. Every serialized series carries a
+//! `version` key, and reading rejects versions newer than this release
+//! instead of misreading them. Versions so far:
+//!
+//! - 0, unversioned: `id`, the price columns, `volume` and `time` of the
+//!   first releases, later joined by the optional `extra`, `labels`,
+//!   `volume_policy` and `instrument`.
+//! - 1: version 0 with the `version` key.
+//!
+//! Version 1 only adds the key, so both versions read through the same
+//! field defaults and there is no migration step. This covers serde formats
+//! that go through these impls, such as JSON. The binary, compressed and
+//! blob formats keep their own layout versions in their headers instead.
+//! With the `json` feature, `Candles::deserialize_any_version` also reads
+//! the row array layout.
+
+use chrono::{DateTime, Utc};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    candle::{Candles, VolumePolicy},
+    extra::ExtraColumns,
+    instrument::InstrumentSpec,
+    symbol::Symbol,
+};
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct CandlesRef<'a> {
+    version: u32,
+    id: &'a Symbol,
+    open: &'a [f64],
+    high: &'a [f64],
+    low: &'a [f64],
+    close: &'a [f64],
+    volume: Option<&'a [f64]>,
+    time: &'a [DateTime<Utc>],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extra: Option<&'a ExtraColumns>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    labels: Option<&'a [u32]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    volume_policy: Option<VolumePolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instrument: Option<InstrumentSpec>,
+}
+
+#[derive(Deserialize)]
+struct CandlesRepr {
+    #[serde(default)]
+    version: u32,
+    #[serde(default)]
+    id: Symbol,
+    open: Vec<f64>,
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+    #[serde(default)]
+    volume: Option<Vec<f64>>,
+    time: Vec<DateTime<Utc>>,
+    #[serde(default)]
+    extra: ExtraColumns,
+    #[serde(default)]
+    labels: Option<Vec<u32>>,
+    #[serde(default)]
+    volume_policy: VolumePolicy,
+    #[serde(default)]
+    instrument: Option<InstrumentSpec>,
+}
+
+impl Serialize for Candles {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        CandlesRef {
+            version: SCHEMA_VERSION,
+            id: &self.id,
+            open: &self.open,
+            high: &self.high,
+            low: &self.low,
+            close: &self.close,
+            volume: self.volume.as_deref(),
+            time: &self.time,
+            extra: (!self.extra.is_empty()).then_some(&self.extra),
+            labels: self.labels.as_deref(),
+            volume_policy: (!self.volume_policy.is_optional()).then_some(self.volume_policy),
+            instrument: self.instrument,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Candles {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = CandlesRepr::deserialize(deserializer)?;
+        if repr.version > SCHEMA_VERSION {
+            return Err(D::Error::custom(format!(
+                "unsupported candles schema version {}",
+                repr.version
+            )));
+        }
+        Ok(Candles {
+            id: repr.id,
+            open: repr.open,
+            high: repr.high,
+            low: repr.low,
+            close: repr.close,
+            volume: repr.volume,
+            time: repr.time,
+            extra: repr.extra,
+            labels: repr.labels,
+            volume_policy: repr.volume_policy,
+            instrument: repr.instrument,
+        })
+    }
+}
+
+#[cfg(feature = "json")]
+impl Candles {
+    /// Reads a JSON series in schema version 0 or 1, or a top-level array in
+    /// the row layout of [`crate::rows`], which older caches used for whole
+    /// series. Newer versions are rejected.
+    pub fn deserialize_any_version(bytes: &[u8]) -> Result<Candles, serde_json::Error> {
+        let value: serde_json::Value = serde_json::from_slice(bytes)?;
+        if value.is_array() {
+            return crate::rows::deserialize(value);
+        }
+        Candles::deserialize(value)
+    }
+}
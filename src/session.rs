@@ -0,0 +1,284 @@
+use std::ops::Range;
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{candle::Candles, resample::LabelReducer};
+
+/// A set of weekdays, with Monday as the lowest bit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Weekdays(u8);
+
+impl Weekdays {
+    pub const NONE: Weekdays = Weekdays(0);
+    pub const MON_FRI: Weekdays = Weekdays(0b0011111);
+    pub const SUN_THU: Weekdays = Weekdays(0b1001111);
+    pub const ALL: Weekdays = Weekdays(0b1111111);
+
+    pub const fn with(self, day: Weekday) -> Weekdays {
+        Weekdays(self.0 | 1 << day.num_days_from_monday())
+    }
+
+    pub const fn without(self, day: Weekday) -> Weekdays {
+        Weekdays(self.0 & !(1 << day.num_days_from_monday()))
+    }
+
+    pub const fn contains(&self, day: Weekday) -> bool {
+        self.0 & 1 << day.num_days_from_monday() != 0
+    }
+}
+
+impl FromIterator<Weekday> for Weekdays {
+    fn from_iter<I: IntoIterator<Item = Weekday>>(iter: I) -> Self {
+        iter.into_iter().fold(Weekdays::NONE, Weekdays::with)
+    }
+}
+
+/// Trading hours in exchange-local time. A session whose close is not after
+/// its open runs overnight, and the weekday mask applies to the day it opens.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TradingSession<Tz: TimeZone> {
+    pub tz: Tz,
+    pub open: NaiveTime,
+    pub close: NaiveTime,
+    pub weekdays: Weekdays,
+}
+
+impl<Tz: TimeZone> TradingSession<Tz> {
+    pub fn new(tz: Tz, open: NaiveTime, close: NaiveTime) -> Self {
+        TradingSession {
+            tz,
+            open,
+            close,
+            weekdays: Weekdays::MON_FRI,
+        }
+    }
+
+    pub fn weekdays(mut self, weekdays: Weekdays) -> Self {
+        self.weekdays = weekdays;
+        self
+    }
+
+    pub fn is_overnight(&self) -> bool {
+        self.close <= self.open
+    }
+
+    /// Local date on which the session containing `time` opened, or `None`
+    /// if `time` falls outside trading hours.
+    pub fn session_date(&self, time: DateTime<Utc>) -> Option<NaiveDate> {
+        let local = time.with_timezone(&self.tz).naive_local();
+        let (date, clock) = (local.date(), local.time());
+        let date = if !self.is_overnight() {
+            (self.open <= clock && clock < self.close).then_some(date)
+        } else if clock >= self.open {
+            Some(date)
+        } else if clock < self.close {
+            date.pred_opt()
+        } else {
+            None
+        }?;
+        self.weekdays.contains(date.weekday()).then_some(date)
+    }
+
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        self.session_date(time).is_some()
+    }
+}
+
+#[cfg(feature = "tz")]
+impl TradingSession<chrono_tz::Tz> {
+    /// CME equity and rates regular trading hours, 08:30-15:00 Chicago.
+    pub fn cme_rth() -> Self {
+        TradingSession::new(
+            chrono_tz::America::Chicago,
+            NaiveTime::from_hms_opt(8, 30, 0).unwrap(),
+            NaiveTime::from_hms_opt(15, 0, 0).unwrap(),
+        )
+    }
+
+    /// CME Globex, 17:00-16:00 Chicago opening Sunday to Thursday.
+    pub fn cme_globex() -> Self {
+        TradingSession::new(
+            chrono_tz::America::Chicago,
+            NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(16, 0, 0).unwrap(),
+        )
+        .weekdays(Weekdays::SUN_THU)
+    }
+
+    /// NYSE and Nasdaq regular trading hours, 09:30-16:00 New York.
+    pub fn us_equities_rth() -> Self {
+        TradingSession::new(
+            chrono_tz::America::New_York,
+            NaiveTime::from_hms_opt(9, 30, 0).unwrap(),
+            NaiveTime::from_hms_opt(16, 0, 0).unwrap(),
+        )
+    }
+
+    /// London FX session, 08:00-17:00 London.
+    pub fn london_fx() -> Self {
+        TradingSession::new(
+            chrono_tz::Europe::London,
+            NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+        )
+    }
+}
+
+impl Candles {
+    /// Keeps the bars whose open time falls inside the session.
+    pub fn filter_session<Tz: TimeZone>(&self, session: &TradingSession<Tz>) -> Candles {
+        let indices = (0..self.time.len())
+            .filter(|&i| session.contains(self.time[i]))
+            .collect::<Vec<_>>();
+        self.gather(&indices)
+    }
+
+    /// Splits the bars inside the session into one series per session date,
+    /// dropping everything outside trading hours.
+    pub fn split_sessions<Tz: TimeZone>(
+        &self,
+        session: &TradingSession<Tz>,
+    ) -> Vec<(NaiveDate, Candles)> {
+        let mut sessions = Vec::new();
+        let mut current: Option<(NaiveDate, usize)> = None;
+        for (i, &time) in self.time.iter().enumerate() {
+            let date = session.session_date(time);
+            match (current, date) {
+                (Some((open, _)), Some(date)) if open == date => continue,
+                (Some((open, start)), _) => sessions.push((open, self.copy_range(start..i))),
+                (None, _) => {}
+            }
+            current = date.map(|date| (date, i));
+        }
+        if let Some((open, start)) = current {
+            sessions.push((open, self.copy_range(start..self.time.len())));
+        }
+        sessions
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AuctionKind {
+    Open,
+    Close,
+}
+
+/// What [`Candles::resample_sessions`] does with auction bars.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AuctionPolicy {
+    /// Folds them into the session bar, as naive resampling does.
+    Merge,
+    /// Drops them.
+    #[default]
+    Exclude,
+    /// Gives each auction its own bar around the session bar.
+    Separate,
+}
+
+/// A trading session plus the local-time windows, half-open, in which its
+/// opening and closing crosses print. The closing window may lie after the
+/// session close.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SessionSpec<Tz: TimeZone> {
+    pub session: TradingSession<Tz>,
+    pub opening_auction: Option<Range<NaiveTime>>,
+    pub closing_auction: Option<Range<NaiveTime>>,
+}
+
+impl<Tz: TimeZone> SessionSpec<Tz> {
+    pub fn new(session: TradingSession<Tz>) -> Self {
+        SessionSpec {
+            session,
+            opening_auction: None,
+            closing_auction: None,
+        }
+    }
+
+    pub fn opening_auction(mut self, window: Range<NaiveTime>) -> Self {
+        self.opening_auction = Some(window);
+        self
+    }
+
+    pub fn closing_auction(mut self, window: Range<NaiveTime>) -> Self {
+        self.closing_auction = Some(window);
+        self
+    }
+
+    /// The auction a bar opening at `time` belongs to, with the local date
+    /// of its session.
+    pub fn auction(&self, time: DateTime<Utc>) -> Option<(AuctionKind, NaiveDate)> {
+        let local = time.with_timezone(&self.session.tz).naive_local();
+        let clock = local.time();
+        let within = |window: &Option<Range<NaiveTime>>| {
+            window
+                .as_ref()
+                .is_some_and(|window| window.contains(&clock))
+        };
+        let (kind, date) = if within(&self.opening_auction) {
+            (AuctionKind::Open, local.date())
+        } else if within(&self.closing_auction) {
+            match self.session.session_date(time) {
+                Some(date) => (AuctionKind::Close, date),
+                None if self.session.is_overnight() && clock < self.session.open => {
+                    (AuctionKind::Close, local.date().pred_opt()?)
+                }
+                None => (AuctionKind::Close, local.date()),
+            }
+        } else {
+            return None;
+        };
+        self.session
+            .weekdays
+            .contains(date.weekday())
+            .then_some((kind, date))
+    }
+}
+
+impl Candles {
+    pub fn mark_auctions<Tz: TimeZone>(&self, spec: &SessionSpec<Tz>) -> Vec<Option<AuctionKind>> {
+        self.time
+            .iter()
+            .map(|&time| spec.auction(time).map(|(kind, _)| kind))
+            .collect()
+    }
+
+    /// One bar per session date, stamped with its first bar, handling
+    /// auction bars by `auctions`. Bars outside the session and its auctions
+    /// are dropped. Call on a sorted series.
+    pub fn resample_sessions<Tz: TimeZone>(
+        &self,
+        spec: &SessionSpec<Tz>,
+        auctions: AuctionPolicy,
+    ) -> Candles {
+        let mut indices = Vec::new();
+        let mut keys: Vec<(NaiveDate, Option<AuctionKind>)> = Vec::new();
+        let mut buckets: Vec<(DateTime<Utc>, Range<usize>)> = Vec::new();
+        for (i, &time) in self.time.iter().enumerate() {
+            let key = match (spec.auction(time), auctions) {
+                (Some(_), AuctionPolicy::Exclude) => continue,
+                (Some((_, date)), AuctionPolicy::Merge) => (date, None),
+                (Some((kind, date)), AuctionPolicy::Separate) => (date, Some(kind)),
+                (None, _) => match spec.session.session_date(time) {
+                    Some(date) => (date, None),
+                    None => continue,
+                },
+            };
+            let n = indices.len();
+            indices.push(i);
+            match (keys.last(), buckets.last_mut()) {
+                (Some(last), Some((_, range))) if *last == key => range.end = n + 1,
+                _ => {
+                    keys.push(key);
+                    buckets.push((time, n..n + 1));
+                }
+            }
+        }
+        self.gather(&indices)
+            .aggregate_buckets(&buckets, LabelReducer::default())
+    }
+}
@@ -0,0 +1,52 @@
+use crate::candle::Candles;
+
+/// Moves values `n` places later, so index `i` holds `xs[i - n]`; negative
+/// `n` moves them earlier. Vacated places are NaN.
+pub fn shift(xs: &[f64], n: isize) -> Vec<f64> {
+    shift_with(xs, n, f64::NAN)
+}
+
+fn shift_with<T: Copy>(xs: &[T], n: isize, fill: T) -> Vec<T> {
+    let len = xs.len();
+    let k = n.unsigned_abs().min(len);
+    let mut out = Vec::with_capacity(len);
+    if n >= 0 {
+        out.resize(k, fill);
+        out.extend_from_slice(&xs[..len - k]);
+    } else {
+        out.extend_from_slice(&xs[k..]);
+        out.resize(len, fill);
+    }
+    out
+}
+
+impl Candles {
+    /// Every column but time moved `n` bars later, or earlier for negative
+    /// `n`, with NaN prices and volume and zero labels in vacated bars. Each
+    /// bar then carries the values of the bar `n` before it.
+    pub fn shift(&self, n: isize) -> Candles {
+        Candles {
+            id: self.id.clone(),
+            open: shift(&self.open, n),
+            high: shift(&self.high, n),
+            low: shift(&self.low, n),
+            close: shift(&self.close, n),
+            volume: self.volume.as_deref().map(|xs| shift(xs, n)),
+            time: self.time.clone(),
+            extra: self.extra.map(|xs| shift(xs, n)),
+            labels: self.labels.as_deref().map(|xs| shift_with(xs, n, 0)),
+            volume_policy: self.volume_policy,
+            instrument: self.instrument,
+        }
+    }
+
+    /// The close `n` bars earlier, NaN for the first `n` bars.
+    pub fn close_lag(&self, n: usize) -> Vec<f64> {
+        shift(&self.close, n.try_into().unwrap_or(isize::MAX))
+    }
+
+    /// The close `n` bars later, NaN for the last `n` bars.
+    pub fn close_lead(&self, n: usize) -> Vec<f64> {
+        shift(&self.close, n.try_into().map_or(isize::MIN, |n: isize| -n))
+    }
+}
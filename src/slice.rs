@@ -0,0 +1,192 @@
+use std::ops::Range;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    candle::{Candle, Candles, CandlesExt, VolumePolicy},
+    extra::ExtraColumns,
+    instrument::InstrumentSpec,
+    symbol::Symbol,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub struct CandlesSlice<'a> {
+    pub id: &'a Symbol,
+    pub open: &'a [f64],
+    pub high: &'a [f64],
+    pub low: &'a [f64],
+    pub close: &'a [f64],
+    pub volume: Option<&'a [f64]>,
+    pub time: &'a [DateTime<Utc>],
+    pub labels: Option<&'a [u32]>,
+    pub(crate) extra: &'a ExtraColumns,
+    pub(crate) offset: usize,
+    pub(crate) volume_policy: VolumePolicy,
+    pub(crate) instrument: Option<InstrumentSpec>,
+}
+
+impl<'a> CandlesSlice<'a> {
+    pub fn len(&self) -> usize {
+        self.time.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.time.is_empty()
+    }
+
+    pub fn slice(&self, range: Range<usize>) -> Option<CandlesSlice<'a>> {
+        if range.start <= range.end && range.end <= self.len() {
+            Some(CandlesSlice {
+                id: self.id,
+                open: &self.open[range.clone()],
+                high: &self.high[range.clone()],
+                low: &self.low[range.clone()],
+                close: &self.close[range.clone()],
+                volume: self.volume.map(|xs| &xs[range.clone()]),
+                time: &self.time[range.clone()],
+                labels: self.labels.map(|xs| &xs[range.clone()]),
+                extra: self.extra,
+                offset: self.offset + range.start,
+                volume_policy: self.volume_policy,
+                instrument: self.instrument,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn to_candles(&self) -> Candles {
+        Candles {
+            id: self.id.clone(),
+            open: self.open.to_vec(),
+            high: self.high.to_vec(),
+            low: self.low.to_vec(),
+            close: self.close.to_vec(),
+            volume: self.volume.map(|xs| xs.to_vec()),
+            time: self.time.to_vec(),
+            extra: self
+                .extra
+                .map(|xs| xs[self.offset..self.offset + self.len()].to_vec()),
+            labels: self.labels.map(|xs| xs.to_vec()),
+            volume_policy: self.volume_policy,
+            instrument: self.instrument,
+        }
+    }
+}
+
+impl CandlesExt for CandlesSlice<'_> {
+    fn get(&self, index: usize) -> Option<Candle> {
+        if index < self.time.len() {
+            Some(Candle {
+                id: self.id.clone(),
+                open: self.open[index],
+                high: self.high[index],
+                low: self.low[index],
+                close: self.close[index],
+                volume: self.volume.map(|xs| xs[index]),
+                time: self.time[index],
+            })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn open(&self) -> &[f64] {
+        self.open
+    }
+
+    #[inline]
+    fn high(&self) -> &[f64] {
+        self.high
+    }
+
+    #[inline]
+    fn low(&self) -> &[f64] {
+        self.low
+    }
+
+    #[inline]
+    fn close(&self) -> &[f64] {
+        self.close
+    }
+
+    #[inline]
+    fn volume(&self) -> Option<&[f64]> {
+        self.volume
+    }
+
+    #[inline]
+    fn time(&self) -> &[DateTime<Utc>] {
+        self.time
+    }
+
+    fn extra(&self, name: &str) -> Option<&[f64]> {
+        self.extra
+            .get(name)
+            .map(|xs| &xs[self.offset..self.offset + self.len()])
+    }
+
+    fn last(&self) -> Option<Candle> {
+        self.len().checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    fn take_last(&self, n: usize) -> Option<Candles> {
+        let len = self.len();
+        if len < n {
+            None
+        } else {
+            self.slice(len - n..len).map(|slice| slice.to_candles())
+        }
+    }
+}
+
+impl Candles {
+    pub fn slice(&self, range: Range<usize>) -> Option<CandlesSlice<'_>> {
+        if range.start <= range.end && range.end <= self.time.len() {
+            Some(self.view(range))
+        } else {
+            None
+        }
+    }
+
+    pub fn as_slice(&self) -> CandlesSlice<'_> {
+        self.view(0..self.time.len())
+    }
+
+    pub(crate) fn view(&self, range: Range<usize>) -> CandlesSlice<'_> {
+        CandlesSlice {
+            id: &self.id,
+            open: &self.open[range.clone()],
+            high: &self.high[range.clone()],
+            low: &self.low[range.clone()],
+            close: &self.close[range.clone()],
+            volume: self.volume.as_ref().map(|xs| &xs[range.clone()]),
+            time: &self.time[range.clone()],
+            labels: self.labels.as_ref().map(|xs| &xs[range.clone()]),
+            extra: &self.extra,
+            offset: range.start,
+            volume_policy: self.volume_policy,
+            instrument: self.instrument,
+        }
+    }
+
+    pub fn windows(&self, n: usize) -> impl Iterator<Item = CandlesSlice<'_>> {
+        let len = self.time.len();
+        let count = if n == 0 || len < n { 0 } else { len - n + 1 };
+        (0..count).map(move |i| self.view(i..i + n))
+    }
+
+    /// Consecutive non-overlapping slices of `n` bars; the last one may be
+    /// shorter.
+    pub fn chunks(&self, n: usize) -> Vec<CandlesSlice<'_>> {
+        if n == 0 {
+            return Vec::new();
+        }
+        let len = self.time.len();
+        (0..len)
+            .step_by(n)
+            .map(|start| self.view(start..(start + n).min(len)))
+            .collect()
+    }
+}
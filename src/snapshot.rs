@@ -0,0 +1,145 @@
+//! Shared, immutable views of a series, handed from a feed thread to readers
+//! without locks.
+
+use std::{ops::Deref, sync::Arc};
+
+use chrono::{DateTime, Utc};
+
+use crate::candle::{Candle, Candles, CandlesExt, PushError};
+
+/// A series behind an [`Arc`]. Cloning only bumps a reference count, and
+/// mutating copies the whole series first if any other clone is alive, so
+/// readers keep the bars they were handed and never wait on the writer. The
+/// cost is one full copy on the first write after each handout that is
+/// still held, paid on the writer's thread; readers that drop their
+/// snapshot before the next write cost nothing.
+///
+/// ```
+/// use std::{sync::mpsc, thread};
+///
+/// use chrono::DateTime;
+/// use erfurt::{candle::Candle, snapshot::CandlesSnapshot};
+///
+/// let candle = |secs| Candle {
+///     open: 1.0,
+///     high: 1.0,
+///     low: 1.0,
+///     close: 1.0,
+///     volume: None,
+///     time: DateTime::from_timestamp(secs, 0).unwrap(),
+///     id: "BTC".into(),
+/// };
+/// let mut feed = CandlesSnapshot::default();
+/// feed.try_push(candle(0)).unwrap();
+///
+/// let (go, wait) = mpsc::channel();
+/// let view = feed.clone();
+/// let reader = thread::spawn(move || {
+///     wait.recv().unwrap();
+///     view.time.len()
+/// });
+/// // The reader holds its snapshot throughout, and the writer is not held up.
+/// for secs in 1..100 {
+///     feed.try_push(candle(secs)).unwrap();
+/// }
+/// go.send(()).unwrap();
+/// assert_eq!(reader.join().unwrap(), 1);
+/// assert_eq!(feed.time.len(), 100);
+/// assert!(!feed.is_shared());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CandlesSnapshot(Arc<Candles>);
+
+impl CandlesSnapshot {
+    pub fn new(candles: Candles) -> Self {
+        CandlesSnapshot(Arc::new(candles))
+    }
+
+    /// True while another clone of this snapshot is alive.
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.0) > 1
+    }
+
+    /// The series for writing, copied first if it is shared.
+    pub fn make_mut(&mut self) -> &mut Candles {
+        Arc::make_mut(&mut self.0)
+    }
+
+    /// [`Candles::try_push`] through [`CandlesSnapshot::make_mut`].
+    pub fn try_push(&mut self, candle: Candle) -> Result<(), PushError> {
+        self.make_mut().try_push(candle)
+    }
+
+    /// The series itself, copied only if it is shared.
+    pub fn restore(self) -> Candles {
+        Arc::unwrap_or_clone(self.0)
+    }
+}
+
+impl From<Candles> for CandlesSnapshot {
+    fn from(candles: Candles) -> Self {
+        CandlesSnapshot::new(candles)
+    }
+}
+
+impl Deref for CandlesSnapshot {
+    type Target = Candles;
+
+    fn deref(&self) -> &Candles {
+        &self.0
+    }
+}
+
+impl AsRef<Candles> for CandlesSnapshot {
+    fn as_ref(&self) -> &Candles {
+        &self.0
+    }
+}
+
+impl Candles {
+    pub fn into_snapshot(self) -> CandlesSnapshot {
+        CandlesSnapshot::new(self)
+    }
+}
+
+impl CandlesExt for CandlesSnapshot {
+    fn get(&self, index: usize) -> Option<Candle> {
+        self.0.get(index)
+    }
+
+    fn open(&self) -> &[f64] {
+        &self.0.open
+    }
+
+    fn high(&self) -> &[f64] {
+        &self.0.high
+    }
+
+    fn low(&self) -> &[f64] {
+        &self.0.low
+    }
+
+    fn close(&self) -> &[f64] {
+        &self.0.close
+    }
+
+    fn volume(&self) -> Option<&[f64]> {
+        self.0.volume.as_deref()
+    }
+
+    fn time(&self) -> &[DateTime<Utc>] {
+        &self.0.time
+    }
+
+    fn extra(&self, name: &str) -> Option<&[f64]> {
+        self.0.extra.get(name)
+    }
+
+    fn last(&self) -> Option<Candle> {
+        self.0.last()
+    }
+
+    fn take_last(&self, n: usize) -> Option<Candles> {
+        self.0.take_last(n)
+    }
+}
@@ -0,0 +1,40 @@
+use crate::{candle::Candles, error::ErfurtError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Keep {
+    First,
+    Last,
+}
+
+impl Candles {
+    /// Fails at the first time that is not after the one before it.
+    pub fn check_sorted(&self) -> Result<(), ErfurtError> {
+        match self.time.windows(2).position(|pair| pair[0] >= pair[1]) {
+            Some(index) => Err(ErfurtError::Unsorted { index: index + 1 }),
+            None => Ok(()),
+        }
+    }
+
+    pub fn sort_by_time(&mut self) {
+        if self.time.windows(2).all(|pair| pair[0] <= pair[1]) {
+            return;
+        }
+        let mut indices = (0..self.time.len()).collect::<Vec<_>>();
+        indices.sort_by_key(|&i| self.time[i]);
+        *self = self.gather(&indices);
+    }
+
+    /// Removes consecutive bars sharing a timestamp, so call on a sorted series.
+    pub fn dedup_by_time(&mut self, keep: Keep) {
+        let len = self.time.len();
+        let indices = (0..len)
+            .filter(|&i| match keep {
+                Keep::First => i == 0 || self.time[i - 1] != self.time[i],
+                Keep::Last => i + 1 == len || self.time[i + 1] != self.time[i],
+            })
+            .collect::<Vec<_>>();
+        if indices.len() != len {
+            *self = self.gather(&indices);
+        }
+    }
+}
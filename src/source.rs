@@ -0,0 +1,20 @@
+//! Async sources of historical candles, such as exchange REST endpoints.
+
+use std::{future::Future, ops::Range};
+
+use chrono::{DateTime, Utc};
+
+use crate::{candle::Candles, symbol::Symbol, timeframe::Timeframe};
+
+pub trait CandleSource {
+    type Error;
+
+    /// Bars of `symbol` opening in `range`, as one sorted series however many
+    /// requests it takes.
+    fn fetch(
+        &self,
+        symbol: &Symbol,
+        timeframe: Timeframe,
+        range: Range<DateTime<Utc>>,
+    ) -> impl Future<Output = Result<Candles, Self::Error>> + Send;
+}
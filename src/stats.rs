@@ -0,0 +1,66 @@
+use chrono::{DateTime, Duration, Utc};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{candle::Candles, kernels};
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CandlesStats {
+    pub count: usize,
+    pub first: DateTime<Utc>,
+    pub last: DateTime<Utc>,
+    pub min_close: f64,
+    pub max_close: f64,
+    pub mean_close: f64,
+    pub total_volume: Option<f64>,
+    pub average_volume: Option<f64>,
+    /// Sample standard deviation of log returns, not annualized.
+    pub volatility: Option<f64>,
+    /// Largest peak-to-trough decline of the close as a fraction of the peak.
+    pub max_drawdown: f64,
+}
+
+impl CandlesStats {
+    pub fn span(&self) -> Duration {
+        self.last - self.first
+    }
+}
+
+pub(crate) fn max_drawdown(xs: &[f64]) -> f64 {
+    let mut peak = f64::NEG_INFINITY;
+    let mut drawdown: f64 = 0.0;
+    for &x in xs {
+        peak = peak.max(x);
+        drawdown = drawdown.max((peak - x) / peak);
+    }
+    drawdown
+}
+
+impl Candles {
+    pub fn stats(&self) -> Option<CandlesStats> {
+        let (first, last) = (*self.time.first()?, *self.time.last()?);
+        let count = self.time.len();
+        let total_volume = self.volume.as_deref().map(kernels::sum);
+        let returns = self.log_returns();
+        let returns = returns.get(1..).unwrap_or_default();
+        let volatility = (returns.len() > 1).then(|| {
+            let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+            let var = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>()
+                / (returns.len() - 1) as f64;
+            var.sqrt()
+        });
+        Some(CandlesStats {
+            count,
+            first,
+            last,
+            min_close: kernels::min(&self.close),
+            max_close: kernels::max(&self.close),
+            mean_close: kernels::sum(&self.close) / count as f64,
+            total_volume,
+            average_volume: total_volume.map(|total| total / count as f64),
+            volatility,
+            max_drawdown: max_drawdown(&self.close),
+        })
+    }
+}
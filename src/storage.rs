@@ -0,0 +1,222 @@
+//! SQLite persistence. Every series shares one table keyed by symbol and
+//! time, the time stored as i64 nanoseconds:
+//!
+//! ```sql
+//! CREATE TABLE candles (
+//!     symbol TEXT NOT NULL,
+//!     time INTEGER NOT NULL,
+//!     open REAL,
+//!     high REAL,
+//!     low REAL,
+//!     close REAL,
+//!     volume REAL,
+//!     PRIMARY KEY (symbol, time)
+//! ) WITHOUT ROWID;
+//! ```
+//!
+//! SQLite stores NaN as NULL, so every value column is nullable and NULL
+//! reads back as NaN; tables created with `NOT NULL` prices by earlier
+//! releases reject NaN prices. DuckDB reads the same file through its
+//! `sqlite` extension. Extra columns are not stored.
+
+use std::{fmt, path::Path};
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+
+use crate::{candle::Candles, symbol::Symbol};
+
+const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS candles (
+    symbol TEXT NOT NULL,
+    time INTEGER NOT NULL,
+    open REAL,
+    high REAL,
+    low REAL,
+    close REAL,
+    volume REAL,
+    PRIMARY KEY (symbol, time)
+) WITHOUT ROWID";
+
+const UPSERT: &str = "INSERT INTO candles (symbol, time, open, high, low, close, volume)
+    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+    ON CONFLICT (symbol, time) DO UPDATE SET
+        open = excluded.open,
+        high = excluded.high,
+        low = excluded.low,
+        close = excluded.close,
+        volume = excluded.volume";
+
+const QUERY: &str = "SELECT time, open, high, low, close, volume FROM candles
+    WHERE symbol = ?1 AND time >= ?2 AND time < ?3
+    ORDER BY time";
+
+#[derive(Debug)]
+pub enum StorageError {
+    Sqlite(rusqlite::Error),
+    /// The time does not fit in i64 nanoseconds.
+    TimeOutOfRange(DateTime<Utc>),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Sqlite(err) => write!(f, "sqlite: {err}"),
+            StorageError::TimeOutOfRange(time) => {
+                write!(f, "time {time} out of the nanosecond range")
+            }
+        }
+    }
+}
+
+impl std::error::Error for StorageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StorageError::Sqlite(err) => Some(err),
+            StorageError::TimeOutOfRange(_) => None,
+        }
+    }
+}
+
+impl From<rusqlite::Error> for StorageError {
+    fn from(err: rusqlite::Error) -> Self {
+        StorageError::Sqlite(err)
+    }
+}
+
+fn nanos(time: DateTime<Utc>) -> Result<i64, StorageError> {
+    time.timestamp_nanos_opt()
+        .ok_or(StorageError::TimeOutOfRange(time))
+}
+
+#[derive(Debug)]
+pub struct CandleStore {
+    conn: Connection,
+}
+
+impl CandleStore {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<CandleStore, StorageError> {
+        CandleStore::from_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<CandleStore, StorageError> {
+        CandleStore::from_connection(Connection::open_in_memory()?)
+    }
+
+    /// Wraps an existing connection, creating the table if it is missing.
+    pub fn from_connection(conn: Connection) -> Result<CandleStore, StorageError> {
+        let store = CandleStore { conn };
+        store.create_tables()?;
+        Ok(store)
+    }
+
+    pub fn create_tables(&self) -> Result<(), StorageError> {
+        self.conn.execute_batch(SCHEMA)?;
+        Ok(())
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Writes every bar in one transaction, replacing bars already stored
+    /// for the same symbol and time. Returns the number of bars written.
+    pub fn upsert(&mut self, candles: &Candles) -> Result<usize, StorageError> {
+        let tx = self.conn.transaction()?;
+        {
+            let mut stmt = tx.prepare_cached(UPSERT)?;
+            for i in 0..candles.time.len() {
+                stmt.execute(params![
+                    candles.id.as_str(),
+                    nanos(candles.time[i])?,
+                    candles.open[i],
+                    candles.high[i],
+                    candles.low[i],
+                    candles.close[i],
+                    candles.volume.as_ref().map(|xs| xs[i]),
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(candles.time.len())
+    }
+
+    /// Bars of `symbol` with `start <= time < end`, in time order. The
+    /// volume column is present when any returned bar has one; bars without
+    /// it read as NaN.
+    pub fn query(
+        &self,
+        symbol: impl Into<Symbol>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Candles, StorageError> {
+        self.query_nanos(symbol.into(), nanos(start)?, nanos(end)?)
+    }
+
+    /// Every stored bar of `symbol`.
+    pub fn load(&self, symbol: impl Into<Symbol>) -> Result<Candles, StorageError> {
+        self.query_nanos(symbol.into(), i64::MIN, i64::MAX)
+    }
+
+    fn query_nanos(&self, symbol: Symbol, start: i64, end: i64) -> Result<Candles, StorageError> {
+        let mut stmt = self.conn.prepare_cached(QUERY)?;
+        let mut rows = stmt.query(params![symbol.as_str(), start, end])?;
+        let mut candles = Candles::with_capacity(symbol, 0, false);
+        let mut volume = Vec::new();
+        let mut has_volume = false;
+        while let Some(row) = rows.next()? {
+            candles
+                .time
+                .push(DateTime::from_timestamp_nanos(row.get(0)?));
+            let price = |index| {
+                row.get::<_, Option<f64>>(index)
+                    .map(|x| x.unwrap_or(f64::NAN))
+            };
+            candles.open.push(price(1)?);
+            candles.high.push(price(2)?);
+            candles.low.push(price(3)?);
+            candles.close.push(price(4)?);
+            let v: Option<f64> = row.get(5)?;
+            has_volume |= v.is_some();
+            volume.push(v.unwrap_or(f64::NAN));
+        }
+        candles.volume = has_volume.then_some(volume);
+        Ok(candles)
+    }
+
+    /// Stored symbols in ascending order.
+    pub fn symbols(&self) -> Result<Vec<Symbol>, StorageError> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT DISTINCT symbol FROM candles ORDER BY symbol")?;
+        let symbols = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .map(|symbol| symbol.map(Symbol::from))
+            .collect::<Result<_, _>>()?;
+        Ok(symbols)
+    }
+
+    /// Open time of the newest stored bar of `symbol`.
+    pub fn last_time(&self, symbol: &str) -> Result<Option<DateTime<Utc>>, StorageError> {
+        let nanos: Option<i64> = self.conn.query_row(
+            "SELECT MAX(time) FROM candles WHERE symbol = ?1",
+            params![symbol],
+            |row| row.get(0),
+        )?;
+        Ok(nanos.map(DateTime::from_timestamp_nanos))
+    }
+
+    /// Deletes the bars of `symbol` with `start <= time < end`, returning how
+    /// many were removed.
+    pub fn delete(
+        &self,
+        symbol: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<usize, StorageError> {
+        let deleted = self.conn.execute(
+            "DELETE FROM candles WHERE symbol = ?1 AND time >= ?2 AND time < ?3",
+            params![symbol, nanos(start)?, nanos(end)?],
+        )?;
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,152 @@
+//! Adapters from async sources of trades or klines to streams of completed
+//! candles. Sources must be `Unpin`; pin others with `Box::pin` first.
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+
+use crate::{
+    bars::{TimeBarBuilder, Trade},
+    candle::{Candle, PushError},
+    snapshot::CandlesSnapshot,
+    symbol::Symbol,
+    timeframe::Timeframe,
+};
+
+/// Completed time bars built from a trade stream; the forming bar is
+/// yielded when the source ends.
+#[derive(Debug)]
+pub struct TradeCandles<S> {
+    source: S,
+    builder: TimeBarBuilder,
+    done: bool,
+}
+
+impl<S: Stream<Item = Trade> + Unpin> Stream for TradeCandles<S> {
+    type Item = Candle;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Candle>> {
+        let this = &mut *self;
+        while !this.done {
+            match Pin::new(&mut this.source).poll_next(cx) {
+                Poll::Ready(Some(trade)) => {
+                    if let Some(candle) = this.builder.push(&trade) {
+                        return Poll::Ready(Some(candle));
+                    }
+                }
+                Poll::Ready(None) => {
+                    this.done = true;
+                    return Poll::Ready(this.builder.finish());
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        Poll::Ready(None)
+    }
+}
+
+/// Klines that exchanges resend while a bar forms, yielded once a later bar
+/// opens. Updates older than the forming bar are dropped; the forming bar is
+/// yielded when the source ends.
+#[derive(Debug)]
+pub struct CompletedKlines<S> {
+    source: S,
+    current: Option<Candle>,
+    done: bool,
+}
+
+impl<S: Stream<Item = Candle> + Unpin> Stream for CompletedKlines<S> {
+    type Item = Candle;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Candle>> {
+        let this = &mut *self;
+        while !this.done {
+            match Pin::new(&mut this.source).poll_next(cx) {
+                Poll::Ready(Some(kline)) => match &this.current {
+                    Some(current) if kline.time > current.time => {
+                        return Poll::Ready(this.current.replace(kline));
+                    }
+                    Some(current) if kline.time < current.time => {}
+                    _ => this.current = Some(kline),
+                },
+                Poll::Ready(None) => {
+                    this.done = true;
+                    return Poll::Ready(this.current.take());
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        Poll::Ready(None)
+    }
+}
+
+/// The series so far after every completed candle, trimmed to the last
+/// `window` bars, or untrimmed for a zero window. Each item shares the
+/// series with the stream, so yielding is free; the next push copies it only
+/// if the previous item is still held, which for a zero window costs the
+/// whole history per held item. A candle that `try_push` rejects is yielded
+/// as its error and leaves the series unchanged.
+#[derive(Debug)]
+pub struct Snapshots<S> {
+    source: S,
+    candles: CandlesSnapshot,
+    window: usize,
+}
+
+impl<S: Stream<Item = Candle> + Unpin> Stream for Snapshots<S> {
+    type Item = Result<CandlesSnapshot, PushError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        match Pin::new(&mut this.source).poll_next(cx) {
+            Poll::Ready(Some(candle)) => {
+                let candles = this.candles.make_mut();
+                if let Err(err) = candles.try_push(candle) {
+                    return Poll::Ready(Some(Err(err)));
+                }
+                let len = candles.time.len();
+                if this.window > 0 && len > this.window {
+                    candles.drain_before(candles.time[len - this.window]);
+                }
+                Poll::Ready(Some(Ok(this.candles.clone())))
+            }
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+pub trait TradeStreamExt: Stream<Item = Trade> + Sized {
+    fn time_bars(self, id: impl Into<Symbol>, timeframe: Timeframe) -> TradeCandles<Self> {
+        TradeCandles {
+            source: self,
+            builder: TimeBarBuilder::new(id, timeframe),
+            done: false,
+        }
+    }
+}
+
+impl<S: Stream<Item = Trade>> TradeStreamExt for S {}
+
+pub trait CandleStreamExt: Stream<Item = Candle> + Sized {
+    fn completed(self) -> CompletedKlines<Self> {
+        CompletedKlines {
+            source: self,
+            current: None,
+            done: false,
+        }
+    }
+
+    fn snapshots(self, window: usize) -> Snapshots<Self> {
+        Snapshots {
+            source: self,
+            candles: CandlesSnapshot::default(),
+            window,
+        }
+    }
+}
+
+impl<S: Stream<Item = Candle>> CandleStreamExt for S {}
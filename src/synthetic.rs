@@ -0,0 +1,162 @@
+use chrono::{DateTime, Utc};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{candle::Candles, symbol::Symbol, timeframe::Timeframe};
+
+/// Parameters are per bar: a volatility of 0.01 moves the log price by about
+/// one percent per bar.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Process {
+    /// Geometric Brownian motion.
+    Gbm { drift: f64, volatility: f64 },
+    /// Mean-reverting price level; `volatility` is in price units and the
+    /// price may go negative.
+    OrnsteinUhlenbeck {
+        mean: f64,
+        reversion: f64,
+        volatility: f64,
+    },
+    /// Geometric Brownian motion whose `(drift, volatility)` jumps to another
+    /// regime with probability `switch` after each bar.
+    RegimeSwitching {
+        regimes: Vec<(f64, f64)>,
+        switch: f64,
+    },
+}
+
+/// Deterministic generator settings; the same seed always yields the same
+/// series.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Synthetic {
+    pub id: Symbol,
+    pub seed: u64,
+    pub start: DateTime<Utc>,
+    pub timeframe: Timeframe,
+    pub price: f64,
+    /// Path steps simulated inside each bar to derive its high and low.
+    pub steps: usize,
+    /// Mean volume per bar, or `None` for no volume column.
+    pub volume: Option<f64>,
+}
+
+impl Default for Synthetic {
+    fn default() -> Self {
+        Synthetic {
+            id: Symbol::default(),
+            seed: 0,
+            start: DateTime::UNIX_EPOCH,
+            timeframe: Timeframe::M1,
+            price: 100.0,
+            steps: 16,
+            volume: Some(1000.0),
+        }
+    }
+}
+
+impl Synthetic {
+    /// Default settings for `id`.
+    pub fn new(id: impl Into<Symbol>) -> Self {
+        Synthetic {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// SplitMix64, kept in-crate so fixtures stay stable across dependency
+/// upgrades.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `(0, 1]`.
+    fn uniform(&mut self) -> f64 {
+        ((self.next_u64() >> 11) + 1) as f64 / (1u64 << 53) as f64
+    }
+
+    fn normal(&mut self) -> f64 {
+        let (u, v) = (self.uniform(), self.uniform());
+        (-2.0 * u.ln()).sqrt() * (std::f64::consts::TAU * v).cos()
+    }
+}
+
+fn gbm(price: f64, drift: f64, volatility: f64, dt: f64, z: f64) -> f64 {
+    price * ((drift - volatility * volatility / 2.0) * dt + volatility * z).exp()
+}
+
+impl Synthetic {
+    pub fn generate(&self, process: &Process, len: usize) -> Candles {
+        let mut rng = Rng(self.seed);
+        let mut candles = Candles::with_capacity(self.id.clone(), len, self.volume.is_some());
+        let steps = self.steps.max(1);
+        let dt = 1.0 / steps as f64;
+        let mut price = self.price;
+        let mut regime = 0;
+        let mut time = self.start;
+        for _ in 0..len {
+            let (open, mut high, mut low) = (price, price, price);
+            for _ in 0..steps {
+                let z = rng.normal() * dt.sqrt();
+                price = match *process {
+                    Process::Gbm { drift, volatility } => gbm(price, drift, volatility, dt, z),
+                    Process::OrnsteinUhlenbeck {
+                        mean,
+                        reversion,
+                        volatility,
+                    } => price + reversion * (mean - price) * dt + volatility * z,
+                    Process::RegimeSwitching { ref regimes, .. } => {
+                        let (drift, volatility) = regimes.get(regime).copied().unwrap_or_default();
+                        gbm(price, drift, volatility, dt, z)
+                    }
+                };
+                high = high.max(price);
+                low = low.min(price);
+            }
+            if let Process::RegimeSwitching {
+                ref regimes,
+                switch,
+            } = *process
+            {
+                if regimes.len() > 1 && rng.uniform() <= switch {
+                    let offset = 1 + (rng.next_u64() % (regimes.len() - 1) as u64) as usize;
+                    regime = (regime + offset) % regimes.len();
+                }
+            }
+            let volume = self
+                .volume
+                .map(|mean| mean * (0.5 * rng.normal() - 0.125).exp());
+            candles.push_row(open, high, low, price, volume, time);
+            time += self.timeframe.duration();
+        }
+        candles
+    }
+}
+
+impl Candles {
+    /// Fixed-seed one-minute GBM series with volume, the shared fixture of
+    /// the benchmark suite.
+    pub fn generate_bench_data(len: usize) -> Candles {
+        let synthetic = Synthetic {
+            seed: 42,
+            steps: 4,
+            ..Synthetic::new("BENCH")
+        };
+        synthetic.generate(
+            &Process::Gbm {
+                drift: 0.0,
+                volatility: 0.001,
+            },
+            len,
+        )
+    }
+}
@@ -0,0 +1,161 @@
+use std::fmt;
+
+use chrono::{TimeZone, Utc};
+
+use crate::{candle::Candles, slice::CandlesSlice};
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Aligned text table of the first and last bars under a shape summary.
+#[derive(Clone, Debug)]
+pub struct Table<'a, Tz: TimeZone = Utc> {
+    candles: CandlesSlice<'a>,
+    head: usize,
+    tail: usize,
+    precision: Option<usize>,
+    tz: Tz,
+}
+
+impl<'a> Table<'a> {
+    pub fn new(candles: CandlesSlice<'a>, head: usize, tail: usize) -> Self {
+        Table {
+            candles,
+            head,
+            tail,
+            precision: None,
+            tz: Utc,
+        }
+    }
+}
+
+impl<'a, Tz: TimeZone> Table<'a, Tz> {
+    /// Digits after the decimal point of every column. By default prices and
+    /// volume follow the instrument, and everything else has four.
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Shows times in `tz` instead of UTC.
+    pub fn with_timezone<T: TimeZone>(self, tz: T) -> Table<'a, T> {
+        Table {
+            candles: self.candles,
+            head: self.head,
+            tail: self.tail,
+            precision: self.precision,
+            tz,
+        }
+    }
+}
+
+impl<Tz: TimeZone> fmt::Display for Table<'_, Tz>
+where
+    Tz::Offset: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let candles = &self.candles;
+        let len = candles.len();
+        let local = |i: usize| candles.time[i].with_timezone(&self.tz);
+
+        let spec = candles.instrument;
+        let price = self
+            .precision
+            .or(spec.map(|spec| spec.price_decimals as usize));
+        let size = self
+            .precision
+            .or(spec.map(|spec| spec.size_decimals() as usize));
+        let (price, size) = (price.unwrap_or(4), size.unwrap_or(4));
+        let mut columns = vec![
+            ("open", candles.open, price),
+            ("high", candles.high, price),
+            ("low", candles.low, price),
+            ("close", candles.close, price),
+        ];
+        columns.extend(candles.volume.map(|xs| ("volume", xs, size)));
+        let offset = candles.offset;
+        let other = self.precision.unwrap_or(4);
+        columns.extend(
+            candles
+                .extra
+                .iter()
+                .map(|(name, xs)| (name, &xs[offset..offset + len], other)),
+        );
+
+        write!(f, "{}: {len} bars x {} columns", candles.id, columns.len())?;
+        if len > 0 {
+            write!(
+                f,
+                ", {} to {}",
+                local(0).format("%Y-%m-%d %H:%M:%S %Z"),
+                local(len - 1).format("%Y-%m-%d %H:%M:%S %Z")
+            )?;
+        }
+        writeln!(f)?;
+
+        let elided = len > self.head + self.tail;
+        let rows = if elided {
+            (0..self.head)
+                .chain(len - self.tail..len)
+                .collect::<Vec<_>>()
+        } else {
+            (0..len).collect()
+        };
+        let mut header = vec![String::new(), "time".to_string()];
+        header.extend(columns.iter().map(|(name, _, _)| name.to_string()));
+        let mut table = vec![header];
+        for &i in &rows {
+            let mut row = vec![i.to_string(), local(i).format(TIME_FORMAT).to_string()];
+            row.extend(
+                columns
+                    .iter()
+                    .map(|(_, xs, digits)| format!("{:.*}", digits, xs[i])),
+            );
+            table.push(row);
+        }
+        let widths = (0..table[0].len())
+            .map(|j| table.iter().map(|row| row[j].len()).max().unwrap_or(0))
+            .collect::<Vec<_>>();
+
+        for (r, row) in table.iter().enumerate() {
+            if elided && r == self.head + 1 {
+                let dots = widths
+                    .iter()
+                    .map(|&width| format!("{:>width$}", "..."))
+                    .collect::<Vec<_>>();
+                writeln!(f, "{}", dots.join("  ").trim_end())?;
+            }
+            let cells = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{cell:>width$}"))
+                .collect::<Vec<_>>();
+            writeln!(f, "{}", cells.join("  "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Candles {
+    pub fn fmt_table(&self, head: usize, tail: usize) -> Table<'_> {
+        Table::new(self.as_slice(), head, tail)
+    }
+}
+
+impl<'a> CandlesSlice<'a> {
+    pub fn fmt_table(&self, head: usize, tail: usize) -> Table<'a> {
+        Table::new(*self, head, tail)
+    }
+}
+
+/// The first and last five bars; see [`Candles::fmt_table`] for other sizes.
+impl fmt::Display for Candles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.fmt_table(5, 5), f)
+    }
+}
+
+impl fmt::Display for CandlesSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.fmt_table(5, 5), f)
+    }
+}
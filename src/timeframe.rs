@@ -0,0 +1,73 @@
+use chrono::{DateTime, Duration, Utc};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Timeframe {
+    seconds: i64,
+}
+
+impl Timeframe {
+    pub const M1: Timeframe = Timeframe::minutes(1);
+    pub const M5: Timeframe = Timeframe::minutes(5);
+    pub const M15: Timeframe = Timeframe::minutes(15);
+    pub const M30: Timeframe = Timeframe::minutes(30);
+    pub const H1: Timeframe = Timeframe::hours(1);
+    pub const H4: Timeframe = Timeframe::hours(4);
+    pub const D1: Timeframe = Timeframe::days(1);
+
+    pub const fn seconds(seconds: i64) -> Self {
+        Timeframe { seconds }
+    }
+
+    pub const fn minutes(minutes: i64) -> Self {
+        Timeframe::seconds(minutes * 60)
+    }
+
+    pub const fn hours(hours: i64) -> Self {
+        Timeframe::minutes(hours * 60)
+    }
+
+    pub const fn days(days: i64) -> Self {
+        Timeframe::hours(days * 24)
+    }
+
+    pub const fn as_seconds(&self) -> i64 {
+        self.seconds
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::seconds(self.seconds)
+    }
+
+    /// Start of the bucket holding `time`, counted from the Unix epoch.
+    /// Non-positive timeframes return `time` unchanged.
+    pub fn floor(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        if self.seconds <= 0 {
+            return time;
+        }
+        let epoch = time.timestamp();
+        DateTime::from_timestamp(epoch - epoch.rem_euclid(self.seconds), 0).unwrap_or(time)
+    }
+
+    /// `time` itself when aligned, else the start of the next bucket.
+    pub fn ceil(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        let floor = self.floor(time);
+        if floor == time {
+            time
+        } else {
+            floor.checked_add_signed(self.duration()).unwrap_or(time)
+        }
+    }
+
+    pub fn is_aligned(&self, time: DateTime<Utc>) -> bool {
+        self.floor(time) == time
+    }
+}
+
+impl From<Duration> for Timeframe {
+    fn from(duration: Duration) -> Self {
+        Timeframe::seconds(duration.num_seconds())
+    }
+}
@@ -0,0 +1,132 @@
+//! Conversions between the UTC time column and other timestamp types, for
+//! callers that keep time as epoch integers or in the `time` crate. These
+//! only move values across the boundary: the column itself stays
+//! `DateTime<Utc>`, so this crate still depends on chrono. For candles
+//! without chrono, use `erfurt-core`, whose times are plain i64.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    candle::{Candle, Candles},
+    symbol::Symbol,
+};
+
+pub trait Timestamp: Sized {
+    fn to_utc(&self) -> Option<DateTime<Utc>>;
+    fn from_utc(time: DateTime<Utc>) -> Option<Self>;
+}
+
+/// Milliseconds since the Unix epoch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EpochMillis(pub i64);
+
+/// Nanoseconds since the Unix epoch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EpochNanos(pub i64);
+
+impl Timestamp for DateTime<Utc> {
+    fn to_utc(&self) -> Option<DateTime<Utc>> {
+        Some(*self)
+    }
+
+    fn from_utc(time: DateTime<Utc>) -> Option<Self> {
+        Some(time)
+    }
+}
+
+impl Timestamp for EpochMillis {
+    fn to_utc(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp_millis(self.0)
+    }
+
+    fn from_utc(time: DateTime<Utc>) -> Option<Self> {
+        Some(EpochMillis(time.timestamp_millis()))
+    }
+}
+
+impl Timestamp for EpochNanos {
+    fn to_utc(&self) -> Option<DateTime<Utc>> {
+        Some(DateTime::from_timestamp_nanos(self.0))
+    }
+
+    fn from_utc(time: DateTime<Utc>) -> Option<Self> {
+        time.timestamp_nanos_opt().map(EpochNanos)
+    }
+}
+
+#[cfg(feature = "time")]
+impl Timestamp for time::OffsetDateTime {
+    fn to_utc(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(self.unix_timestamp(), self.nanosecond())
+    }
+
+    fn from_utc(time: DateTime<Utc>) -> Option<Self> {
+        let nanos = i128::from(time.timestamp()) * 1_000_000_000
+            + i128::from(time.timestamp_subsec_nanos());
+        time::OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimestampError {
+    /// The value at `index` has no representation in the target type.
+    OutOfRange { index: usize },
+}
+
+impl fmt::Display for TimestampError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimestampError::OutOfRange { index } => {
+                write!(f, "timestamp at index {index} is out of range")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TimestampError {}
+
+impl Candle {
+    pub fn time_as<T: Timestamp>(&self) -> Result<T, TimestampError> {
+        T::from_utc(self.time).ok_or(TimestampError::OutOfRange { index: 0 })
+    }
+}
+
+impl Candles {
+    pub fn time_as<T: Timestamp>(&self) -> Result<Vec<T>, TimestampError> {
+        self.time
+            .iter()
+            .enumerate()
+            .map(|(index, &time)| T::from_utc(time).ok_or(TimestampError::OutOfRange { index }))
+            .collect()
+    }
+
+    /// Builds a series from columns timed in any [`Timestamp`] type. The
+    /// columns are taken as given; call [`Candles::validate`] to check them.
+    pub fn from_columns<T: Timestamp>(
+        id: impl Into<Symbol>,
+        time: &[T],
+        open: Vec<f64>,
+        high: Vec<f64>,
+        low: Vec<f64>,
+        close: Vec<f64>,
+        volume: Option<Vec<f64>>,
+    ) -> Result<Candles, TimestampError> {
+        let time = time
+            .iter()
+            .enumerate()
+            .map(|(index, time)| time.to_utc().ok_or(TimestampError::OutOfRange { index }))
+            .collect::<Result<_, _>>()?;
+        Ok(Candles {
+            id: id.into(),
+            open,
+            high,
+            low,
+            close,
+            volume,
+            time,
+            ..Default::default()
+        })
+    }
+}
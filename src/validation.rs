@@ -0,0 +1,164 @@
+use std::fmt;
+
+use crate::candle::{Candles, VolumePolicy};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    ColumnLength {
+        column: &'static str,
+        len: usize,
+        expected: usize,
+    },
+    ExtraColumnLength {
+        name: String,
+        len: usize,
+        expected: usize,
+    },
+    NonFinite {
+        index: usize,
+        column: &'static str,
+    },
+    HighBelowBody {
+        index: usize,
+    },
+    LowAboveBody {
+        index: usize,
+    },
+    NonIncreasingTime {
+        index: usize,
+    },
+    /// A bar does not open on a boundary of the expected timeframe.
+    Misaligned {
+        index: usize,
+    },
+    /// The volume policy is `Required` but the series has no volume column.
+    MissingVolume,
+    /// A bar carries volume but the series has no volume column.
+    UnexpectedVolume {
+        index: usize,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::ColumnLength {
+                column,
+                len,
+                expected,
+            } => write!(f, "column {column} has {len} values, expected {expected}"),
+            ValidationError::ExtraColumnLength {
+                name,
+                len,
+                expected,
+            } => write!(
+                f,
+                "extra column {name} has {len} values, expected {expected}"
+            ),
+            ValidationError::NonFinite { index, column } => {
+                write!(f, "non-finite {column} at index {index}")
+            }
+            ValidationError::HighBelowBody { index } => {
+                write!(f, "high below max(open, close) at index {index}")
+            }
+            ValidationError::LowAboveBody { index } => {
+                write!(f, "low above min(open, close) at index {index}")
+            }
+            ValidationError::NonIncreasingTime { index } => {
+                write!(f, "time not strictly increasing at index {index}")
+            }
+            ValidationError::Misaligned { index } => {
+                write!(f, "time not on a timeframe boundary at index {index}")
+            }
+            ValidationError::MissingVolume => {
+                write!(f, "volume is required but the series has no volume column")
+            }
+            ValidationError::UnexpectedVolume { index } => {
+                write!(f, "volume at index {index} without a volume column")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl Candles {
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        let expected = self.time.len();
+        let mut columns = vec![
+            ("open", &self.open),
+            ("high", &self.high),
+            ("low", &self.low),
+            ("close", &self.close),
+        ];
+        if let Some(volume) = &self.volume {
+            columns.push(("volume", volume));
+        }
+        for (column, xs) in &columns {
+            if xs.len() != expected {
+                errors.push(ValidationError::ColumnLength {
+                    column,
+                    len: xs.len(),
+                    expected,
+                });
+            }
+        }
+        for (name, xs) in self.extra.iter() {
+            if xs.len() != expected {
+                errors.push(ValidationError::ExtraColumnLength {
+                    name: name.to_string(),
+                    len: xs.len(),
+                    expected,
+                });
+            }
+        }
+        if self.volume_policy == VolumePolicy::Required && self.volume.is_none() {
+            errors.push(ValidationError::MissingVolume);
+        }
+        if let Some(labels) = self.labels.as_ref().filter(|xs| xs.len() != expected) {
+            errors.push(ValidationError::ColumnLength {
+                column: "labels",
+                len: labels.len(),
+                expected,
+            });
+        }
+        let len = columns
+            .iter()
+            .map(|(_, xs)| xs.len())
+            .fold(expected, usize::min);
+        for index in 0..len {
+            let mut finite = true;
+            for (column, xs) in &columns {
+                if !xs[index].is_finite() {
+                    finite = false;
+                    errors.push(ValidationError::NonFinite { index, column });
+                }
+            }
+            if finite {
+                let (open, high, low, close) = (
+                    self.open[index],
+                    self.high[index],
+                    self.low[index],
+                    self.close[index],
+                );
+                if high < open.max(close) {
+                    errors.push(ValidationError::HighBelowBody { index });
+                }
+                if low > open.min(close) {
+                    errors.push(ValidationError::LowAboveBody { index });
+                }
+            }
+        }
+        for index in 1..expected {
+            if self.time[index] <= self.time[index - 1] {
+                errors.push(ValidationError::NonIncreasingTime { index });
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Rolling volatility estimators, per bar and not annualized: multiply by
+//! the square root of bars per year to annualize. Ranges assume positive
+//! prices.
+
+use std::f64::consts::LN_2;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{candle::Candles, parallel::map_indices};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VolEstimator {
+    /// Sample deviation of close-to-close log returns.
+    CloseToClose,
+    /// High-low range; assumes no drift and no opening gaps.
+    Parkinson,
+    /// Open, high, low and close; assumes no drift and no opening gaps.
+    GarmanKlass,
+    /// Drift-independent; assumes no opening gaps.
+    RogersSatchell,
+    /// Combines overnight, open-to-close and Rogers-Satchell variances,
+    /// handling both drift and opening gaps.
+    YangZhang,
+}
+
+/// Sample variance.
+fn variance(xs: impl Iterator<Item = f64> + Clone) -> f64 {
+    let n = xs.clone().count() as f64;
+    let mean = xs.clone().sum::<f64>() / n;
+    xs.map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+}
+
+impl Candles {
+    fn rogers_satchell(&self, i: usize) -> f64 {
+        let (o, h, l, c) = (self.open[i], self.high[i], self.low[i], self.close[i]);
+        (h / c).ln() * (h / o).ln() + (l / c).ln() * (l / o).ln()
+    }
+
+    /// Volatility over each trailing window of `period` bars, NaN until the
+    /// first full window. Close-to-close and Yang-Zhang use the previous
+    /// close, so their first window ends one bar later.
+    pub fn realized_volatility(&self, estimator: VolEstimator, period: usize) -> Vec<f64> {
+        let len = self.time.len();
+        let lag = match estimator {
+            VolEstimator::CloseToClose | VolEstimator::YangZhang => 1,
+            _ => 0,
+        };
+        let min_period = match estimator {
+            VolEstimator::CloseToClose | VolEstimator::YangZhang => 2,
+            _ => 1,
+        };
+        if period < min_period || len < period + lag {
+            return vec![f64::NAN; len];
+        }
+        let n = period as f64;
+        let first = period + lag - 1;
+        let mut out = vec![f64::NAN; first];
+        out.extend(map_indices(len - first, |k| {
+            let window = k + lag..k + lag + period;
+            let variance = match estimator {
+                VolEstimator::CloseToClose => {
+                    variance(window.map(|i| (self.close[i] / self.close[i - 1]).ln()))
+                }
+                VolEstimator::Parkinson => {
+                    window
+                        .map(|i| (self.high[i] / self.low[i]).ln().powi(2))
+                        .sum::<f64>()
+                        / (4.0 * LN_2 * n)
+                }
+                VolEstimator::GarmanKlass => {
+                    window
+                        .map(|i| {
+                            0.5 * (self.high[i] / self.low[i]).ln().powi(2)
+                                - (2.0 * LN_2 - 1.0) * (self.close[i] / self.open[i]).ln().powi(2)
+                        })
+                        .sum::<f64>()
+                        / n
+                }
+                VolEstimator::RogersSatchell => {
+                    window.map(|i| self.rogers_satchell(i)).sum::<f64>() / n
+                }
+                VolEstimator::YangZhang => {
+                    let overnight = variance(
+                        window
+                            .clone()
+                            .map(|i| (self.open[i] / self.close[i - 1]).ln()),
+                    );
+                    let intraday =
+                        variance(window.clone().map(|i| (self.close[i] / self.open[i]).ln()));
+                    let rs = window.map(|i| self.rogers_satchell(i)).sum::<f64>() / n;
+                    let k = 0.34 / (1.34 + (n + 1.0) / (n - 1.0));
+                    overnight + k * intraday + (1.0 - k) * rs
+                }
+            };
+            variance.sqrt()
+        }));
+        out
+    }
+}
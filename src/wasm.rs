@@ -0,0 +1,113 @@
+//! JavaScript bindings through `wasm-bindgen`. Columns cross the boundary
+//! as `Float64Array`s and times as epoch milliseconds, the unit of `Date`.
+//! The manifest builds only an rlib; build the module with `cargo rustc
+//! --lib --crate-type cdylib --target wasm32-unknown-unknown --features wasm`.
+
+use chrono::DateTime;
+use wasm_bindgen::prelude::*;
+
+use crate::{candle::Candles, validation::ValidationError};
+
+/// `Candles` as the JavaScript class `Candles`.
+#[wasm_bindgen(js_name = Candles)]
+#[derive(Clone, Debug)]
+pub struct JsCandles(Candles);
+
+fn invalid(errors: Vec<ValidationError>) -> JsError {
+    let errors = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+    JsError::new(&errors.join("; "))
+}
+
+#[wasm_bindgen(js_class = Candles)]
+impl JsCandles {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        id: &str,
+        time: &[f64],
+        open: Vec<f64>,
+        high: Vec<f64>,
+        low: Vec<f64>,
+        close: Vec<f64>,
+        volume: Option<Vec<f64>>,
+    ) -> Result<JsCandles, JsError> {
+        let time = time
+            .iter()
+            .map(|&millis| {
+                DateTime::from_timestamp_millis(millis as i64)
+                    .ok_or_else(|| JsError::new(&format!("time {millis} is out of range")))
+            })
+            .collect::<Result<_, _>>()?;
+        let candles = Candles {
+            id: id.into(),
+            open,
+            high,
+            low,
+            close,
+            volume,
+            time,
+            ..Default::default()
+        };
+        candles.validate().map_err(invalid)?;
+        Ok(JsCandles(candles))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> String {
+        self.0.id.to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.0.time.len()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn time(&self) -> Vec<f64> {
+        self.0
+            .time
+            .iter()
+            .map(|time| time.timestamp_millis() as f64)
+            .collect()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn open(&self) -> Vec<f64> {
+        self.0.open.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn high(&self) -> Vec<f64> {
+        self.0.high.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn low(&self) -> Vec<f64> {
+        self.0.low.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn close(&self) -> Vec<f64> {
+        self.0.close.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn volume(&self) -> Option<Vec<f64>> {
+        self.0.volume.clone()
+    }
+
+    pub fn extra(&self, name: &str) -> Option<Vec<f64>> {
+        self.0.extra.get(name).map(<[f64]>::to_vec)
+    }
+}
+
+impl From<Candles> for JsCandles {
+    fn from(candles: Candles) -> Self {
+        JsCandles(candles)
+    }
+}
+
+impl From<JsCandles> for Candles {
+    fn from(candles: JsCandles) -> Self {
+        candles.0
+    }
+}
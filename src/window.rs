@@ -0,0 +1,176 @@
+//! The last `N` bars in inline arrays, for hot paths that never look further
+//! back. A push onto a full window shifts every column down by one, which
+//! for the small `N` this is meant for is cheaper than a ring buffer that
+//! cannot hand out contiguous slices.
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    candle::{check_push, Candle, Candles, CandlesExt, PushError},
+    symbol::Symbol,
+};
+
+#[derive(Clone, Debug)]
+pub struct CandleWindow<const N: usize> {
+    id: Symbol,
+    open: [f64; N],
+    high: [f64; N],
+    low: [f64; N],
+    close: [f64; N],
+    volume: Option<[f64; N]>,
+    time: [DateTime<Utc>; N],
+    len: usize,
+}
+
+impl<const N: usize> CandleWindow<N> {
+    pub fn new(id: impl Into<Symbol>, has_volume: bool) -> Self {
+        CandleWindow {
+            id: id.into(),
+            open: [f64::NAN; N],
+            high: [f64::NAN; N],
+            low: [f64::NAN; N],
+            close: [f64::NAN; N],
+            volume: has_volume.then_some([f64::NAN; N]),
+            time: [DateTime::<Utc>::MIN_UTC; N],
+            len: 0,
+        }
+    }
+
+    pub fn id(&self) -> &Symbol {
+        &self.id
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Validates like [`Candles::try_push`], evicting the oldest bar when
+    /// full. An empty window takes its id and volume column from the first
+    /// candle, as an empty series does.
+    pub fn push(&mut self, candle: Candle) -> Result<(), PushError> {
+        let adopt = check_push(
+            &self.id,
+            self.volume.is_some(),
+            self.time().last().copied(),
+            &candle.id,
+            candle.time,
+            candle.volume.is_some(),
+        )?;
+        if adopt.id {
+            self.id = candle.id.clone();
+        }
+        if adopt.volume {
+            self.volume = Some([f64::NAN; N]);
+        }
+        if N == 0 {
+            return Ok(());
+        }
+        if self.is_full() {
+            self.open.copy_within(1.., 0);
+            self.high.copy_within(1.., 0);
+            self.low.copy_within(1.., 0);
+            self.close.copy_within(1.., 0);
+            if let Some(xs) = self.volume.as_mut() {
+                xs.copy_within(1.., 0);
+            }
+            self.time.copy_within(1.., 0);
+            self.len -= 1;
+        }
+        let i = self.len;
+        self.open[i] = candle.open;
+        self.high[i] = candle.high;
+        self.low[i] = candle.low;
+        self.close[i] = candle.close;
+        if let (Some(xs), Some(volume)) = (self.volume.as_mut(), candle.volume) {
+            xs[i] = volume;
+        }
+        self.time[i] = candle.time;
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn to_candles(&self) -> Candles {
+        self.take_last(self.len).unwrap()
+    }
+}
+
+impl<const N: usize> CandlesExt for CandleWindow<N> {
+    fn get(&self, index: usize) -> Option<Candle> {
+        (index < self.len).then(|| Candle {
+            id: self.id.clone(),
+            open: self.open[index],
+            high: self.high[index],
+            low: self.low[index],
+            close: self.close[index],
+            volume: self.volume.as_ref().map(|xs| xs[index]),
+            time: self.time[index],
+        })
+    }
+
+    #[inline]
+    fn open(&self) -> &[f64] {
+        &self.open[..self.len]
+    }
+
+    #[inline]
+    fn high(&self) -> &[f64] {
+        &self.high[..self.len]
+    }
+
+    #[inline]
+    fn low(&self) -> &[f64] {
+        &self.low[..self.len]
+    }
+
+    #[inline]
+    fn close(&self) -> &[f64] {
+        &self.close[..self.len]
+    }
+
+    #[inline]
+    fn volume(&self) -> Option<&[f64]> {
+        self.volume.as_ref().map(|xs| &xs[..self.len])
+    }
+
+    #[inline]
+    fn time(&self) -> &[DateTime<Utc>] {
+        &self.time[..self.len]
+    }
+
+    fn last(&self) -> Option<Candle> {
+        self.get(self.len.checked_sub(1)?)
+    }
+
+    fn take_last(&self, n: usize) -> Option<Candles> {
+        if self.len < n {
+            return None;
+        }
+        let range = self.len - n..self.len;
+        Some(Candles {
+            id: self.id.clone(),
+            open: self.open[range.clone()].to_vec(),
+            high: self.high[range.clone()].to_vec(),
+            low: self.low[range.clone()].to_vec(),
+            close: self.close[range.clone()].to_vec(),
+            volume: self.volume.as_ref().map(|xs| xs[range.clone()].to_vec()),
+            time: self.time[range].to_vec(),
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,124 @@
+use std::ops::Range;
+
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+
+use crate::{
+    candle::Candles,
+    resample::{CalendarPeriod, LabelReducer},
+    slice::CandlesSlice,
+};
+
+/// Local date a calendar period starts on.
+pub type PeriodKey = NaiveDate;
+
+/// Candles read in a local time zone. Storage stays UTC; only the calendar
+/// arithmetic changes, so daily bars follow the exchange day.
+#[derive(Clone, Debug)]
+pub struct ZonedCandles<'a, Tz: TimeZone> {
+    candles: &'a Candles,
+    tz: Tz,
+}
+
+impl<'a, Tz: TimeZone> ZonedCandles<'a, Tz> {
+    pub fn candles(&self) -> &'a Candles {
+        self.candles
+    }
+
+    pub fn tz(&self) -> &Tz {
+        &self.tz
+    }
+
+    /// Open time of bar `index` in local time.
+    pub fn time(&self, index: usize) -> Option<DateTime<Tz>> {
+        self.candles
+            .time
+            .get(index)
+            .map(|time| time.with_timezone(&self.tz))
+    }
+
+    pub fn times(&self) -> impl Iterator<Item = DateTime<Tz>> + '_ {
+        self.candles
+            .time
+            .iter()
+            .map(|time| time.with_timezone(&self.tz))
+    }
+
+    /// Local date of each bar.
+    pub fn dates(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.times().map(|time| time.date_naive())
+    }
+
+    /// Runs of consecutive bars sharing a local date.
+    pub fn day_boundaries(&self) -> Vec<(NaiveDate, Range<usize>)> {
+        self.period_boundaries(CalendarPeriod::Day)
+    }
+
+    /// Runs of consecutive bars in the same local calendar period.
+    pub fn period_boundaries(&self, period: CalendarPeriod) -> Vec<(PeriodKey, Range<usize>)> {
+        let mut periods: Vec<(PeriodKey, Range<usize>)> = Vec::new();
+        for (i, date) in self.dates().enumerate() {
+            let key = period.start_date(date);
+            match periods.last_mut() {
+                Some((last, range)) if *last == key => range.end = i + 1,
+                _ => periods.push((key, i..i + 1)),
+            }
+        }
+        periods
+    }
+
+    /// Borrowed bars of each local calendar period, such as the intraday
+    /// session of every exchange day.
+    pub fn group_by_period(&self, period: CalendarPeriod) -> Vec<(PeriodKey, CandlesSlice<'a>)> {
+        self.period_boundaries(period)
+            .into_iter()
+            .map(|(key, range)| (key, self.candles.view(range)))
+            .collect()
+    }
+
+    /// One bar per local day, stamped with the UTC instant of local
+    /// midnight. Where midnight falls in a DST gap the bar keeps the time
+    /// of its first bar.
+    pub fn resample_daily(&self) -> Candles {
+        self.resample_daily_with(LabelReducer::default())
+    }
+
+    /// Like [`ZonedCandles::resample_daily`], combining labels with `labels`.
+    pub fn resample_daily_with(&self, labels: LabelReducer) -> Candles {
+        let buckets = self
+            .day_boundaries()
+            .into_iter()
+            .map(|(date, range)| {
+                let midnight = date.and_time(Default::default());
+                let start = self
+                    .tz
+                    .from_local_datetime(&midnight)
+                    .earliest()
+                    .map_or(self.candles.time[range.start], |time| {
+                        time.with_timezone(&Utc)
+                    });
+                (start, range)
+            })
+            .collect::<Vec<_>>();
+        self.candles.aggregate_buckets(&buckets, labels)
+    }
+}
+
+impl Candles {
+    pub fn with_timezone<Tz: TimeZone>(&self, tz: Tz) -> ZonedCandles<'_, Tz> {
+        ZonedCandles { candles: self, tz }
+    }
+
+    /// Runs of consecutive bars sharing a date in `tz`.
+    pub fn day_boundaries<Tz: TimeZone>(&self, tz: Tz) -> Vec<(NaiveDate, Range<usize>)> {
+        self.with_timezone(tz).day_boundaries()
+    }
+
+    /// Borrowed bars of each calendar period in `tz`.
+    pub fn group_by_period<Tz: TimeZone>(
+        &self,
+        period: CalendarPeriod,
+        tz: Tz,
+    ) -> Vec<(PeriodKey, CandlesSlice<'_>)> {
+        self.with_timezone(tz).group_by_period(period)
+    }
+}